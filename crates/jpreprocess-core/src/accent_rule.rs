@@ -123,6 +123,7 @@ impl FromStr for POSMatch {
     type Err = JPreprocessError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
+            "*" => Ok(Self::Default),
             "動詞" => Ok(Self::Doushi),
             "助詞" => Ok(Self::Joshi),
             "形容詞" => Ok(Self::Keiyoushi),
@@ -133,6 +134,18 @@ impl FromStr for POSMatch {
     }
 }
 
+impl Display for POSMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match &self {
+            Self::Default => "*",
+            Self::Doushi => "動詞",
+            Self::Joshi => "助詞",
+            Self::Keiyoushi => "形容詞",
+            Self::Meishi => "名詞",
+        })
+    }
+}
+
 #[derive(Clone, PartialEq, Serialize, Deserialize, Debug, Default)]
 pub struct ChainRules {
     default: Option<ChainRule>,
@@ -159,6 +172,12 @@ impl ChainRules {
 
     fn push_rule(&mut self, rule_str: &str) -> JPreprocessResult<()> {
         let (pos, rule) = Self::parse_rule(rule_str)?;
+        self.set_rule(pos, rule);
+        Ok(())
+    }
+
+    /// Sets the rule applied when the next word matches `pos`, replacing the existing one.
+    pub fn set_rule(&mut self, pos: POSMatch, rule: ChainRule) {
         match pos {
             POSMatch::Doushi => self.doushi.replace(rule),
             POSMatch::Joshi => self.joshi.replace(rule),
@@ -166,7 +185,19 @@ impl ChainRules {
             POSMatch::Meishi => self.meishi.replace(rule),
             POSMatch::Default => self.default.replace(rule),
         };
-        Ok(())
+    }
+
+    /// Iterates over the rules which are set, together with the POS they apply to.
+    pub fn iter(&self) -> impl Iterator<Item = (POSMatch, &ChainRule)> {
+        [
+            (POSMatch::Default, &self.default),
+            (POSMatch::Doushi, &self.doushi),
+            (POSMatch::Joshi, &self.joshi),
+            (POSMatch::Keiyoushi, &self.keiyoushi),
+            (POSMatch::Meishi, &self.meishi),
+        ]
+        .into_iter()
+        .filter_map(|(pos, rule)| rule.as_ref().map(|rule| (pos, rule)))
     }
 
     fn parse_rule(rule: &str) -> JPreprocessResult<(POSMatch, ChainRule)> {
@@ -9,6 +9,10 @@ pub enum JPreprocessErrorKind {
     CTypeParseError,
     CFormParseError,
     AccentRuleParseError,
+    WordEntryFormatError,
+    NormalizeRuleParseError,
+    MarkupParseError,
+    LabelFormatError,
 }
 
 impl JPreprocessErrorKind {
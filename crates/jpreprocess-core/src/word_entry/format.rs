@@ -0,0 +1,277 @@
+//! Versioned binary encoding of [`WordEntry`].
+//!
+//! - Version 0 is the plain bincode representation of [`WordEntry`],
+//!   which was used before the format got versioned.
+//!   As it stores enums by their variant index, it can only be read as long as
+//!   the layout of `POS`, `CType`, `CForm` and `ChainRules` is unchanged.
+//! - Version 1 and later start with a magic byte (`0xfe`) and the version number,
+//!   and store every enum by its name as written in dictionary csv.
+//!
+//! Version 0 data always starts with `0x00` or `0x01` (the variant index of [`WordEntry`]),
+//! so it never collides with the magic byte.
+
+use std::str::FromStr;
+
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    accent_rule::{AccentType, ChainRule, ChainRules, POSMatch},
+    cform::CForm,
+    ctype::CType,
+    error::JPreprocessErrorKind,
+    pos::POS,
+    pronunciation::{Mora, Pronunciation},
+    word_details::WordDetails,
+    JPreprocessResult,
+};
+
+use super::WordEntry;
+
+/// The format version written by [`WordEntry::to_bytes`].
+pub const WORD_ENTRY_FORMAT_VERSION: u8 = 1;
+
+const MAGIC: u8 = 0xfe;
+
+fn bincode_options() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_varint_encoding()
+        .allow_trailing_bytes()
+}
+
+#[derive(Serialize, Deserialize)]
+struct TaggedDetails {
+    /// `None` for [`WordEntry::Single`]
+    orig: Option<String>,
+    pos: String,
+    ctype: String,
+    cform: String,
+    read: Option<String>,
+    /// (mora, is_voiced)
+    pron: Vec<(String, bool)>,
+    acc: i32,
+    mora_size: i32,
+    /// (pos, accent_type, add_type)
+    chain_rule: Vec<(String, String, i32)>,
+    chain_flag: Option<bool>,
+}
+
+impl TaggedDetails {
+    fn new(orig: Option<&str>, details: &WordDetails) -> Self {
+        Self {
+            orig: orig.map(|orig| orig.to_string()),
+            pos: details.pos.to_string(),
+            ctype: details.ctype.to_string(),
+            cform: details.cform.to_string(),
+            read: details.read.to_owned(),
+            pron: details
+                .pron
+                .moras()
+                .iter()
+                .map(|mora| {
+                    let tag = Mora {
+                        mora_enum: mora.mora_enum,
+                        is_voiced: true,
+                    };
+                    (tag.to_string(), mora.is_voiced)
+                })
+                .collect(),
+            acc: details.acc,
+            mora_size: details.mora_size,
+            chain_rule: details
+                .chain_rule
+                .iter()
+                .map(|(pos, rule)| (pos.to_string(), rule.accent_type.to_string(), rule.add_type))
+                .collect(),
+            chain_flag: details.chain_flag,
+        }
+    }
+
+    fn into_details(self) -> JPreprocessResult<(Option<String>, WordDetails)> {
+        let pos = {
+            // Some POS (e.g. `名詞,*`) omit the trailing `*` groups.
+            let mut groups: Vec<&str> = self.pos.split(',').collect();
+            if groups.len() > 4 {
+                return Err(JPreprocessErrorKind::PartOfSpeechParseError
+                    .with_error(anyhow::anyhow!("POS has too many groups: {}", self.pos)));
+            }
+            groups.resize(4, "*");
+            POS::from_strs(groups[0], groups[1], groups[2], groups[3])?
+        };
+
+        let pron = self
+            .pron
+            .iter()
+            .map(
+                |(tag, is_voiced)| match Pronunciation::from_str(tag)?.moras() {
+                    [mora] => Ok(Mora {
+                        mora_enum: mora.mora_enum,
+                        is_voiced: *is_voiced,
+                    }),
+                    _ => Err(JPreprocessErrorKind::PronunciationParseError
+                        .with_error(anyhow::anyhow!("{} is not a single mora", tag))),
+                },
+            )
+            .collect::<JPreprocessResult<Vec<Mora>>>()?;
+
+        let mut chain_rule = ChainRules::default();
+        for (pos, accent_type, add_type) in self.chain_rule {
+            let accent_type = AccentType::from_str(&accent_type).map_err(|_| {
+                JPreprocessErrorKind::AccentRuleParseError
+                    .with_error(anyhow::anyhow!("Unknown accent type {}", accent_type))
+            })?;
+            chain_rule.set_rule(
+                POSMatch::from_str(&pos)?,
+                ChainRule::new(accent_type, add_type),
+            );
+        }
+
+        Ok((
+            self.orig,
+            WordDetails {
+                pos,
+                ctype: CType::from_str(&self.ctype)?,
+                cform: CForm::from_str(&self.cform)?,
+                read: self.read,
+                pron: Pronunciation::new(pron),
+                acc: self.acc,
+                mora_size: self.mora_size,
+                chain_rule,
+                chain_flag: self.chain_flag,
+            },
+        ))
+    }
+}
+
+impl WordEntry {
+    /// Encodes the entry with the latest format version ([`WORD_ENTRY_FORMAT_VERSION`]).
+    pub fn to_bytes(&self) -> JPreprocessResult<Vec<u8>> {
+        let tagged: Vec<TaggedDetails> = match self {
+            Self::Single(details) => vec![TaggedDetails::new(None, details)],
+            Self::Multiple(details_vec) => details_vec
+                .iter()
+                .map(|(orig, details)| TaggedDetails::new(Some(orig), details))
+                .collect(),
+        };
+
+        let mut result = vec![MAGIC, WORD_ENTRY_FORMAT_VERSION];
+        bincode_options()
+            .serialize_into(&mut result, &tagged)
+            .map_err(|err| JPreprocessErrorKind::WordEntryFormatError.with_error(err))?;
+        Ok(result)
+    }
+
+    /// Decodes the entry encoded with any of the supported format versions.
+    pub fn from_bytes(data: &[u8]) -> JPreprocessResult<Self> {
+        match Self::format_version(data) {
+            0 => bincode::deserialize(data)
+                .map_err(|err| JPreprocessErrorKind::WordEntryFormatError.with_error(err)),
+            1 => {
+                let tagged: Vec<TaggedDetails> = bincode_options()
+                    .deserialize(&data[2..])
+                    .map_err(|err| JPreprocessErrorKind::WordEntryFormatError.with_error(err))?;
+                let mut details_vec = tagged
+                    .into_iter()
+                    .map(TaggedDetails::into_details)
+                    .collect::<JPreprocessResult<Vec<_>>>()?;
+
+                match details_vec.as_slice() {
+                    [(None, _)] => Ok(Self::Single(details_vec.pop().unwrap().1)),
+                    _ => details_vec
+                        .into_iter()
+                        .map(|(orig, details)| match orig {
+                            Some(orig) => Ok((orig, details)),
+                            None => Err(JPreprocessErrorKind::WordEntryFormatError
+                                .with_error(anyhow::anyhow!("orig is missing in multiple entry"))),
+                        })
+                        .collect::<JPreprocessResult<_>>()
+                        .map(Self::Multiple),
+                }
+            }
+            version => Err(
+                JPreprocessErrorKind::WordEntryFormatError.with_error(anyhow::anyhow!(
+                    "Word entry format version {} is not supported. Please update jpreprocess.",
+                    version
+                )),
+            ),
+        }
+    }
+
+    /// Returns the format version of the encoded entry.
+    pub fn format_version(data: &[u8]) -> u8 {
+        match data {
+            [MAGIC, version, ..] => *version,
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        accent_rule::{AccentType, ChainRule, ChainRules, POSMatch},
+        pronunciation::Pronunciation,
+        word_entry::WordEntry,
+    };
+
+    use super::WORD_ENTRY_FORMAT_VERSION;
+
+    fn load(row: &str) -> WordEntry {
+        let mut details: Vec<&str> = row.split(',').collect();
+        details.resize(12, "");
+        WordEntry::load(&details).unwrap()
+    }
+
+    #[test]
+    fn single() {
+        let entry = load("名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2/3,C1");
+        let bytes = entry.to_bytes().unwrap();
+        assert_eq!(WordEntry::format_version(&bytes), WORD_ENTRY_FORMAT_VERSION);
+        assert_eq!(WordEntry::from_bytes(&bytes).unwrap(), entry);
+    }
+
+    #[test]
+    fn multiple() {
+        let entry = load("感動詞,*,*,*,*,*,あー:あ,アー:ア,アー:ア,1/2:1/1,C1");
+        let bytes = entry.to_bytes().unwrap();
+        assert_eq!(WordEntry::from_bytes(&bytes).unwrap(), entry);
+    }
+
+    #[test]
+    fn conjugation_and_unvoiced() {
+        let entry = load(
+            "動詞,自立,*,*,五段・カ行イ音便,連用タ接続,聞く,キイ,キ’イ,1/2,動詞%F2@1/形容詞%F1",
+        );
+        let bytes = entry.to_bytes().unwrap();
+        assert_eq!(WordEntry::from_bytes(&bytes).unwrap(), entry);
+    }
+
+    #[test]
+    fn not_representable_in_csv() {
+        let mut chain_rule = ChainRules::default();
+        chain_rule.set_rule(POSMatch::Default, ChainRule::new(AccentType::None, -1));
+
+        let mut entry = WordEntry::default();
+        if let WordEntry::Single(details) = &mut entry {
+            details.chain_rule = chain_rule;
+            details.pron = Pronunciation::default();
+        }
+        let bytes = entry.to_bytes().unwrap();
+        assert_eq!(WordEntry::from_bytes(&bytes).unwrap(), entry);
+    }
+
+    #[test]
+    fn legacy() {
+        let entry = load("記号,句点,*,*,*,*,。,。,。,0/0,*");
+        let bytes = bincode::serialize(&entry).unwrap();
+        assert_eq!(WordEntry::format_version(&bytes), 0);
+        assert_eq!(WordEntry::from_bytes(&bytes).unwrap(), entry);
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let mut bytes = WordEntry::default().to_bytes().unwrap();
+        bytes[1] = WORD_ENTRY_FORMAT_VERSION + 1;
+        assert!(WordEntry::from_bytes(&bytes).is_err());
+    }
+}
@@ -4,6 +4,10 @@ use crate::{
 };
 use serde::{Deserialize, Serialize};
 
+mod format;
+
+pub use format::WORD_ENTRY_FORMAT_VERSION;
+
 #[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
 pub enum WordEntry {
     Single(WordDetails),
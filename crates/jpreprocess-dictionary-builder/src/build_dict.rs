@@ -11,8 +11,8 @@ use yada::{builder::DoubleArrayBuilder, DoubleArray};
 
 use crate::serializer::DictionarySerializer;
 
-const SIMPLE_USERDIC_FIELDS_NUM: usize = 3;
-const DETAILED_USERDIC_FIELDS_NUM: usize = 13;
+pub(crate) const SIMPLE_USERDIC_FIELDS_NUM: usize = 3;
+pub(crate) const DETAILED_USERDIC_FIELDS_NUM: usize = 13;
 
 const SIMPLE_WORD_COST: i16 = -10000;
 const SIMPLE_CONTEXT_ID: u16 = 0;
@@ -36,6 +36,21 @@ where
         .collect()
 }
 
+/// Returns the columns identifying an entry: surface, POS, ctype and cform.
+///
+/// Two rows with the same key are regarded as the same word, possibly with different
+/// costs, readings or accents.
+/// For simple user dictionary rows, the key is surface and POS.
+pub(crate) fn entry_key(row: &[String]) -> Vec<String> {
+    if row.len() == SIMPLE_USERDIC_FIELDS_NUM {
+        row[0..2].to_vec()
+    } else {
+        let mut key = row[0..1].to_vec();
+        key.extend(row.iter().skip(4).take(6).cloned());
+        key
+    }
+}
+
 pub fn build_word_entry_map(
     rows: &Vec<Vec<String>>,
     is_system: bool,
@@ -60,7 +75,7 @@ pub fn build_word_entry_map(
     for entry in entries {
         word_entry_map
             .entry(entry.surface)
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(entry.word_entry);
     }
 
@@ -135,6 +150,28 @@ impl WordEntryWithString {
     }
 }
 
+/// Builds the table of right_id, indexed by word id.
+///
+/// Lindera assumes that right_id equals to left_id, so right_id is not
+/// stored in the dictionary itself.
+/// This table is only used to restore the dictionary csv.
+pub fn build_right_ids(rows: &Vec<Vec<String>>, is_system: bool) -> LinderaResult<Vec<u8>> {
+    let mut buffer = Vec::with_capacity(rows.len() * 2);
+    for row in rows {
+        let right_id = if !is_system && row.len() == SIMPLE_USERDIC_FIELDS_NUM {
+            SIMPLE_CONTEXT_ID
+        } else {
+            u16::from_str(row[2].trim()).map_err(|_err| {
+                LinderaErrorKind::Parse.with_error(anyhow::anyhow!("failed to parse right_id"))
+            })?
+        };
+        buffer
+            .write_u16::<LittleEndian>(right_id)
+            .map_err(|err| LinderaErrorKind::Io.with_error(err))?;
+    }
+    Ok(buffer)
+}
+
 pub fn build_words(
     serializer: &(dyn DictionarySerializer + Send + Sync),
     rows: &Vec<Vec<String>>,
@@ -0,0 +1,68 @@
+//! Compression of built dictionary files.
+//!
+//! The output is a bincode-serialized [`CompressedData`], which records the algorithm
+//! together with the data, so it can be read back with [`lindera_decompress::decompress`]
+//! regardless of the algorithm chosen at build time.
+
+use std::io::Write;
+
+use flate2::{
+    write::{DeflateEncoder, GzEncoder, ZlibEncoder},
+    Compression,
+};
+use lindera_core::{error::LinderaErrorKind, LinderaResult};
+use lindera_decompress::{Algorithm, CompressedData};
+
+pub fn compress(data: &[u8], algorithm: Algorithm) -> LinderaResult<Vec<u8>> {
+    let compressed = match algorithm {
+        Algorithm::Deflate => {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+            encoder.write_all(data).and_then(|_| encoder.finish())
+        }
+        Algorithm::Zlib => {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+            encoder.write_all(data).and_then(|_| encoder.finish())
+        }
+        Algorithm::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+            encoder.write_all(data).and_then(|_| encoder.finish())
+        }
+        Algorithm::Raw => Ok(data.to_vec()),
+    }
+    .map_err(|err| LinderaErrorKind::Compress.with_error(anyhow::anyhow!(err)))?;
+
+    bincode::serialize(&CompressedData::new(algorithm, compressed))
+        .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))
+}
+
+/// Reads the data written by [`compress`].
+pub fn decompress(data: &[u8]) -> LinderaResult<Vec<u8>> {
+    let compressed: CompressedData = bincode::deserialize(data)
+        .map_err(|err| LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!(err)))?;
+    lindera_decompress::decompress(compressed)
+        .map_err(|err| LinderaErrorKind::Decode.with_error(err))
+}
+
+#[cfg(test)]
+mod tests {
+    use lindera_decompress::Algorithm;
+
+    use super::{compress, decompress};
+
+    #[test]
+    fn round_trip() {
+        let data = "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2/3,C1\n"
+            .repeat(100)
+            .into_bytes();
+        for algorithm in [
+            Algorithm::Deflate,
+            Algorithm::Zlib,
+            Algorithm::Gzip,
+            Algorithm::Raw,
+        ] {
+            let compressed = compress(&data, algorithm).unwrap();
+            assert_eq!(decompress(&compressed).unwrap(), data);
+        }
+        assert!(compress(&data, Algorithm::Deflate).unwrap().len() < data.len());
+    }
+}
@@ -0,0 +1,141 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::build_dict::entry_key;
+
+/// A difference between two dictionary csv.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffEntry {
+    /// The row only exists in the new dictionary.
+    Added(Vec<String>),
+    /// The row only exists in the old dictionary.
+    Removed(Vec<String>),
+    /// The word (surface, POS, ctype and cform) exists in both dictionaries,
+    /// but the other columns differ.
+    Changed(Vec<String>, Vec<String>),
+}
+
+impl Display for DiffEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Added(row) => write!(f, "+ {}", row.join(",")),
+            Self::Removed(row) => write!(f, "- {}", row.join(",")),
+            Self::Changed(old, new) => write!(f, "- {}\n+ {}", old.join(","), new.join(",")),
+        }
+    }
+}
+
+/// Rows of the same word in (old, new)
+type RowGroup<'a> = (Vec<&'a [String]>, Vec<&'a [String]>);
+
+/// Compares the rows of two dictionaries.
+///
+/// Rows are matched by [surface and details](crate::build_dict::entry_key),
+/// and rows present in both dictionaries are omitted.
+/// The result is ordered by the first appearance of the word in `old`, then in `new`.
+pub fn diff_rows(old: &[Vec<String>], new: &[Vec<String>]) -> Vec<DiffEntry> {
+    let mut keys: Vec<Vec<String>> = vec![];
+    let mut groups: HashMap<Vec<String>, RowGroup> = HashMap::new();
+    for (row, is_new) in old
+        .iter()
+        .map(|row| (row, false))
+        .chain(new.iter().map(|row| (row, true)))
+    {
+        let key = entry_key(row);
+        let group = groups.entry(key.clone()).or_insert_with(|| {
+            keys.push(key);
+            (vec![], vec![])
+        });
+        if is_new {
+            group.1.push(row);
+        } else {
+            group.0.push(row);
+        }
+    }
+
+    let mut result = vec![];
+    for key in keys {
+        let (mut old_rows, mut new_rows) = groups.remove(&key).unwrap();
+
+        old_rows.retain(
+            |old_row| match new_rows.iter().position(|new_row| new_row == old_row) {
+                Some(index) => {
+                    new_rows.remove(index);
+                    false
+                }
+                None => true,
+            },
+        );
+
+        let changed = old_rows.len().min(new_rows.len());
+        for (old_row, new_row) in old_rows.iter().zip(new_rows.iter()) {
+            result.push(DiffEntry::Changed(old_row.to_vec(), new_row.to_vec()));
+        }
+        result.extend(
+            old_rows[changed..]
+                .iter()
+                .map(|row| DiffEntry::Removed(row.to_vec())),
+        );
+        result.extend(
+            new_rows[changed..]
+                .iter()
+                .map(|row| DiffEntry::Added(row.to_vec())),
+        );
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff_rows, DiffEntry};
+
+    fn rows(rows: &[&str]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.split(',').map(String::from).collect())
+            .collect()
+    }
+
+    #[test]
+    fn diff() {
+        let old = rows(&[
+            "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2/3,C1",
+            "東京,1354,1354,3000,名詞,固有名詞,地域,一般,*,*,東京,トウキョウ,トーキョー,0/4,C1",
+            "クーバネティス,名詞,クーバネティス",
+        ]);
+        let new = rows(&[
+            "東京,1354,1354,3000,名詞,固有名詞,地域,一般,*,*,東京,トウキョウ,トーキョー,0/4,C1",
+            "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニッポン,ニッポン,3/4,C1",
+            "大阪,1354,1354,3000,名詞,固有名詞,地域,一般,*,*,大阪,オオサカ,オーサカ,0/4,C1",
+        ]);
+
+        assert_eq!(
+            diff_rows(&old, &new),
+            [
+                DiffEntry::Changed(old[0].clone(), new[1].clone()),
+                DiffEntry::Removed(old[2].clone()),
+                DiffEntry::Added(new[2].clone()),
+            ]
+        );
+    }
+
+    #[test]
+    fn same_surface() {
+        let old = rows(&[
+            "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2/3,C1",
+            "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニッポン,ニッポン,3/4,C1",
+        ]);
+        let new = rows(&[
+            "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニッポン,ニッポン,3/4,C1",
+            "日本,1285,1285,10000,名詞,一般,*,*,*,*,日本,ニホン,ニホン,2/3,C1",
+        ]);
+
+        assert_eq!(
+            diff_rows(&old, &new),
+            [
+                DiffEntry::Removed(old[0].clone()),
+                DiffEntry::Added(new[1].clone()),
+            ]
+        );
+        assert!(diff_rows(&old, &old).is_empty());
+    }
+}
@@ -4,7 +4,6 @@ use std::{
     ops::Deref,
     path::{Path, PathBuf},
     str::FromStr,
-    u32,
 };
 
 use rayon::prelude::*;
@@ -29,12 +28,20 @@ use crate::{
     serializer::{DictionarySerializer, LinderaSerializer},
 };
 
+/// The file name of the right_id table in a system dictionary directory.
+pub const RIGHT_ID_FILE: &str = "dict.rightid";
+
+/// Returns the path of the right_id table of the user dictionary at `dict_path`.
+pub fn user_right_id_path(dict_path: &Path) -> PathBuf {
+    dict_path.with_extension("rightid")
+}
+
 pub struct IpadicBuilder {
     serializer: Box<dyn DictionarySerializer + Send + Sync>,
 }
 
 impl IpadicBuilder {
-    const UNK_FIELDS_NUM: usize = 11;
+    pub(crate) const UNK_FIELDS_NUM: usize = 11;
 
     pub fn new(serializer: Box<dyn Send + Sync + DictionarySerializer>) -> Self {
         IpadicBuilder { serializer }
@@ -70,21 +77,128 @@ impl IpadicBuilder {
         Ok(())
     }
 
-    pub fn build_user_dict_from_data(
+    /// Builds a system dictionary like [`DictionaryBuilder::build_dictionary`],
+    /// but uses `rows` instead of the csv files in `input_dir`.
+    ///
+    /// `input_dir` must still contain char.def, unk.def and matrix.def.
+    pub fn build_dictionary_from_rows(
         &self,
-        rows: &Vec<Vec<&str>>,
+        input_dir: &Path,
+        rows: &Vec<Vec<String>>,
+        output_dir: &Path,
+    ) -> LinderaResult<()> {
+        fs::create_dir_all(output_dir)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+        let chardef = self.build_chardef(input_dir, output_dir)?;
+        self.build_unk(input_dir, &chardef, output_dir)?;
+        self.build_dict_from_rows(normalize_rows(rows), output_dir)?;
+        self.build_cost_matrix(input_dir, output_dir)?;
+
+        Ok(())
+    }
+
+    /// Builds a user dictionary from `rows` and writes it to `output_file`.
+    pub fn build_user_dictionary_from_rows(
+        &self,
+        rows: &Vec<Vec<String>>,
+        output_file: &Path,
+    ) -> LinderaResult<()> {
+        let sorted_rows = sort_rows(normalize_rows(rows));
+        let user_dict = self.build_user_dict_from_sorted_rows(&sorted_rows)?;
+        write_user_dict(&user_dict, &sorted_rows, output_file)
+    }
+
+    pub(crate) fn build_unk_from_data(
+        &self,
+        unk_data: &str,
+        chardef: &CharacterDefinitions,
+        output_dir: &Path,
+    ) -> LinderaResult<()> {
+        let unknown_dictionary = parse_unk(chardef.categories(), unk_data, Self::UNK_FIELDS_NUM)?;
+
+        let mut unk_buffer = Vec::new();
+        bincode::serialize_into(&mut unk_buffer, &unknown_dictionary)
+            .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
+
+        let wtr_unk_path = output_dir.join(Path::new("unk.bin"));
+        let mut wtr_unk = io::BufWriter::new(
+            File::create(wtr_unk_path)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
+        );
+        write(&unk_buffer, &mut wtr_unk)?;
+        wtr_unk
+            .flush()
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+        Ok(())
+    }
+
+    pub(crate) fn build_dict_from_rows(
+        &self,
+        normalized_rows: Vec<Vec<String>>,
+        output_dir: &Path,
+    ) -> LinderaResult<()> {
+        let normalized_rows = sort_rows(normalized_rows);
+
+        let wtr_da_path = output_dir.join(Path::new("dict.da"));
+        let mut wtr_da = io::BufWriter::new(
+            File::create(wtr_da_path)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
+        );
+
+        let wtr_vals_path = output_dir.join(Path::new("dict.vals"));
+        let mut wtr_vals = io::BufWriter::new(
+            File::create(wtr_vals_path)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
+        );
+
+        self.write_words(
+            output_dir.join(Path::new("dict.words")).as_path(),
+            output_dir.join(Path::new("dict.wordsidx")).as_path(),
+            true,
+            &normalized_rows,
+        )?;
+
+        let prefix_dict = build_prefix_dict(build_word_entry_map(&normalized_rows, true)?, true)?;
+
+        write(&prefix_dict.da.0, &mut wtr_da)?;
+
+        write(&prefix_dict.vals_data, &mut wtr_vals)?;
+
+        wtr_vals
+            .flush()
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+        write_file(
+            &output_dir.join(RIGHT_ID_FILE),
+            &build_right_ids(&normalized_rows, true)?,
+        )?;
+
+        Ok(())
+    }
+
+    pub(crate) fn build_user_dict_from_sorted_rows(
+        &self,
+        normalized_rows: &Vec<Vec<String>>,
     ) -> LinderaResult<UserDictionary> {
-        let mut normalized_rows: Vec<Vec<String>> = normalize_rows(rows);
-        normalized_rows.par_sort_by_key(|row| row.get(0).map(|s| s.to_string()));
         let (words_idx_data, words_data) =
-            build_words(self.serializer.deref(), &normalized_rows, false)?;
-        let dict = build_prefix_dict(build_word_entry_map(&normalized_rows, false)?, false)?;
+            build_words(self.serializer.deref(), normalized_rows, false)?;
+        let dict = build_prefix_dict(build_word_entry_map(normalized_rows, false)?, false)?;
+
         Ok(UserDictionary {
             dict,
             words_idx_data,
             words_data,
         })
     }
+
+    pub fn build_user_dict_from_data(
+        &self,
+        rows: &Vec<Vec<&str>>,
+    ) -> LinderaResult<UserDictionary> {
+        self.build_user_dict_from_sorted_rows(&sort_rows(normalize_rows(rows)))
+    }
 }
 
 impl Default for IpadicBuilder {
@@ -107,29 +221,7 @@ impl DictionaryBuilder for IpadicBuilder {
     }
 
     fn build_user_dictionary(&self, input_file: &Path, output_file: &Path) -> LinderaResult<()> {
-        let parent_dir = match output_file.parent() {
-            Some(parent_dir) => parent_dir,
-            None => {
-                return Err(LinderaErrorKind::Io.with_error(anyhow::anyhow!(
-                    "failed to get parent directory of output file"
-                )))
-            }
-        };
-        fs::create_dir_all(parent_dir)
-            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
-
-        let user_dict = self.build_user_dict(input_file)?;
-
-        let mut wtr = io::BufWriter::new(
-            File::create(output_file)
-                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
-        );
-        bincode::serialize_into(&mut wtr, &user_dict)
-            .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
-        wtr.flush()
-            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
-
-        Ok(())
+        self.build_user_dictionary_from_rows(&read_csv_rows(input_file)?, output_file)
     }
 
     fn build_chardef(
@@ -174,143 +266,49 @@ impl DictionaryBuilder for IpadicBuilder {
         debug!("reading {:?}", unk_data_path);
 
         let unk_data = read_utf8_file(&unk_data_path)?;
-        let unknown_dictionary = parse_unk(chardef.categories(), &unk_data, Self::UNK_FIELDS_NUM)?;
-
-        let mut unk_buffer = Vec::new();
-        bincode::serialize_into(&mut unk_buffer, &unknown_dictionary)
-            .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
-
-        let wtr_unk_path = output_dir.join(Path::new("unk.bin"));
-        let mut wtr_unk = io::BufWriter::new(
-            File::create(wtr_unk_path)
-                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
-        );
-        write(&unk_buffer, &mut wtr_unk)?;
-        wtr_unk
-            .flush()
-            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
-
-        Ok(())
+        self.build_unk_from_data(&unk_data, chardef, output_dir)
     }
 
     fn build_dict(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
-        let pattern = if let Some(path) = input_dir.to_str() {
-            format!("{}/*.csv", path)
-        } else {
-            return Err(
-                LinderaErrorKind::Io.with_error(anyhow::anyhow!("Failed to convert path to &str."))
-            );
-        };
-
-        let mut filenames: Vec<PathBuf> = Vec::new();
-        for entry in
-            glob(&pattern).map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?
-        {
-            match entry {
-                Ok(path) => {
-                    if let Some(filename) = path.file_name() {
-                        filenames.push(Path::new(input_dir).join(filename));
-                    } else {
-                        return Err(LinderaErrorKind::Io
-                            .with_error(anyhow::anyhow!("failed to get filename")));
-                    }
-                }
-                Err(err) => return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(err))),
-            }
-        }
-
-        let mut rows: Vec<StringRecord> = vec![];
-        for filename in filenames {
-            debug!("reading {:?}", filename);
-
-            let mut rdr = csv::ReaderBuilder::new()
-                .has_headers(false)
-                .from_path(filename)
-                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
-
-            for result in rdr.records() {
-                let record = result
-                    .map_err(|err| LinderaErrorKind::Content.with_error(anyhow::anyhow!(err)))?;
-                rows.push(record);
-            }
-        }
-
-        let mut normalized_rows: Vec<Vec<String>> = normalize_rows(&rows);
-
-        normalized_rows.par_sort_by_key(|row| row.get(0).map(|s| s.to_string()));
-
-        let wtr_da_path = output_dir.join(Path::new("dict.da"));
-        let mut wtr_da = io::BufWriter::new(
-            File::create(wtr_da_path)
-                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
-        );
-
-        let wtr_vals_path = output_dir.join(Path::new("dict.vals"));
-        let mut wtr_vals = io::BufWriter::new(
-            File::create(wtr_vals_path)
-                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
-        );
-
-        self.write_words(
-            output_dir.join(Path::new("dict.words")).as_path(),
-            output_dir.join(Path::new("dict.wordsidx")).as_path(),
-            true,
-            &normalized_rows,
-        )?;
-
-        let prefix_dict = build_prefix_dict(build_word_entry_map(&normalized_rows, true)?, true)?;
+        self.build_dict_from_rows(read_dict_csv_rows(input_dir)?, output_dir)
+    }
 
-        write(&prefix_dict.da.0, &mut wtr_da)?;
+    fn build_cost_matrix(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
+        read_cost_matrix(input_dir)?.write(output_dir)
+    }
 
-        write(&prefix_dict.vals_data, &mut wtr_vals)?;
+    fn build_user_dict(&self, input_file: &Path) -> LinderaResult<UserDictionary> {
+        self.build_user_dict_from_sorted_rows(&sort_rows(read_csv_rows(input_file)?))
+    }
+}
 
-        wtr_vals
-            .flush()
-            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+/// Connection costs read from matrix.def.
+pub(crate) struct CostMatrix {
+    pub forward_size: u32,
+    pub backward_size: u32,
+    /// Indexed by `backward_id + forward_id * backward_size`
+    pub costs: Vec<i16>,
+}
 
-        Ok(())
+impl CostMatrix {
+    pub fn cost(&self, forward_id: u16, backward_id: u16) -> i16 {
+        self.costs[backward_id as usize + forward_id as usize * self.backward_size as usize]
     }
 
-    fn build_cost_matrix(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
-        let matrix_data_path = input_dir.join("matrix.def");
-        debug!("reading {:?}", matrix_data_path);
-
-        let matrix_data = read_utf8_file(&matrix_data_path)?;
-        let mut lines_it = matrix_data
-            .par_lines()
-            .map(|line| {
-                line.split_whitespace()
-                    .map(i32::from_str)
-                    .collect::<Result<Vec<i32>, _>>()
-                    .map_err(|err| LinderaErrorKind::Parse.with_error(anyhow::anyhow!(err)))
-            })
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter();
-        let header = lines_it.next().ok_or_else(|| {
-            LinderaErrorKind::Content.with_error(anyhow::anyhow!("unknown error"))
-        })?;
-        let forward_size = header[0] as u32;
-        let backward_size = header[1] as u32;
-        let len = 2 + (forward_size * backward_size) as usize;
-        let mut costs = vec![i16::MAX; len];
-        costs[0] = forward_size as i16;
-        costs[1] = backward_size as i16;
-        for fields in lines_it {
-            let forward_id = fields[0] as u32;
-            let backward_id = fields[1] as u32;
-            let cost = fields[2] as u16;
-            costs[2 + (backward_id + forward_id * backward_size) as usize] = cost as i16;
-        }
-
+    /// Writes matrix.mtx to `output_dir`.
+    pub fn write(&self, output_dir: &Path) -> LinderaResult<()> {
         let wtr_matrix_mtx_path = output_dir.join(Path::new("matrix.mtx"));
         let mut wtr_matrix_mtx = io::BufWriter::new(
             File::create(wtr_matrix_mtx_path)
                 .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
         );
         let mut matrix_mtx_buffer = Vec::new();
-        for cost in costs {
+        for cost in [self.forward_size as i16, self.backward_size as i16]
+            .iter()
+            .chain(&self.costs)
+        {
             matrix_mtx_buffer
-                .write_i16::<LittleEndian>(cost)
+                .write_i16::<LittleEndian>(*cost)
                 .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
         }
         write(&matrix_mtx_buffer, &mut wtr_matrix_mtx)?;
@@ -321,35 +319,155 @@ impl DictionaryBuilder for IpadicBuilder {
 
         Ok(())
     }
+}
 
-    fn build_user_dict(&self, input_file: &Path) -> LinderaResult<UserDictionary> {
-        debug!("reading {:?}", input_file);
+/// Reads matrix.def in the system dictionary source directory.
+pub(crate) fn read_cost_matrix(input_dir: &Path) -> LinderaResult<CostMatrix> {
+    let matrix_data_path = input_dir.join("matrix.def");
+    debug!("reading {:?}", matrix_data_path);
+
+    let matrix_data = read_utf8_file(&matrix_data_path)?;
+    let mut lines_it = matrix_data
+        .par_lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(i32::from_str)
+                .collect::<Result<Vec<i32>, _>>()
+                .map_err(|err| LinderaErrorKind::Parse.with_error(anyhow::anyhow!(err)))
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter();
+    let header = lines_it
+        .next()
+        .ok_or_else(|| LinderaErrorKind::Content.with_error(anyhow::anyhow!("unknown error")))?;
+    let forward_size = header[0] as u32;
+    let backward_size = header[1] as u32;
+    let mut costs = vec![i16::MAX; (forward_size * backward_size) as usize];
+    for fields in lines_it {
+        let forward_id = fields[0] as u32;
+        let backward_id = fields[1] as u32;
+        let cost = fields[2] as u16;
+        costs[(backward_id + forward_id * backward_size) as usize] = cost as i16;
+    }
+
+    Ok(CostMatrix {
+        forward_size,
+        backward_size,
+        costs,
+    })
+}
+
+/// Reads all csv files in the system dictionary source directory and returns the normalized rows.
+pub fn read_dict_csv_rows(input_dir: &Path) -> LinderaResult<Vec<Vec<String>>> {
+    let pattern = if let Some(path) = input_dir.to_str() {
+        format!("{}/*.csv", path)
+    } else {
+        return Err(
+            LinderaErrorKind::Io.with_error(anyhow::anyhow!("Failed to convert path to &str."))
+        );
+    };
+
+    let mut filenames: Vec<PathBuf> = Vec::new();
+    for entry in
+        glob(&pattern).map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?
+    {
+        match entry {
+            Ok(path) => {
+                if let Some(filename) = path.file_name() {
+                    filenames.push(Path::new(input_dir).join(filename));
+                } else {
+                    return Err(
+                        LinderaErrorKind::Io.with_error(anyhow::anyhow!("failed to get filename"))
+                    );
+                }
+            }
+            Err(err) => return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(err))),
+        }
+    }
+
+    let mut rows: Vec<StringRecord> = vec![];
+    for filename in filenames {
+        debug!("reading {:?}", filename);
 
         let mut rdr = csv::ReaderBuilder::new()
             .has_headers(false)
-            .flexible(true)
-            .from_path(input_file)
+            .from_path(filename)
             .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
 
-        let mut rows: Vec<StringRecord> = vec![];
         for result in rdr.records() {
             let record =
                 result.map_err(|err| LinderaErrorKind::Content.with_error(anyhow::anyhow!(err)))?;
             rows.push(record);
         }
+    }
 
-        let mut normalized_rows: Vec<Vec<String>> = normalize_rows(&rows);
-        normalized_rows.par_sort_by_key(|row| row.get(0).map(|s| s.to_string()));
-        let (words_idx_data, words_data) =
-            build_words(self.serializer.deref(), &normalized_rows, false)?;
-        let dict = build_prefix_dict(build_word_entry_map(&normalized_rows, false)?, false)?;
+    Ok(normalize_rows(&rows))
+}
 
-        Ok(UserDictionary {
-            dict,
-            words_idx_data,
-            words_data,
-        })
+/// Reads a (user) dictionary csv and returns the normalized rows.
+pub fn read_csv_rows(input_file: &Path) -> LinderaResult<Vec<Vec<String>>> {
+    debug!("reading {:?}", input_file);
+
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(input_file)
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+    let mut rows: Vec<StringRecord> = vec![];
+    for result in rdr.records() {
+        let record =
+            result.map_err(|err| LinderaErrorKind::Content.with_error(anyhow::anyhow!(err)))?;
+        rows.push(record);
     }
+
+    Ok(normalize_rows(&rows))
+}
+
+/// Sorts rows by surface. The index of the sorted rows is the word id.
+pub(crate) fn sort_rows(mut normalized_rows: Vec<Vec<String>>) -> Vec<Vec<String>> {
+    normalized_rows.par_sort_by_key(|row| row.first().map(|s| s.to_string()));
+    normalized_rows
+}
+
+fn write_user_dict(
+    user_dict: &UserDictionary,
+    normalized_rows: &Vec<Vec<String>>,
+    output_file: &Path,
+) -> LinderaResult<()> {
+    let parent_dir = match output_file.parent() {
+        Some(parent_dir) => parent_dir,
+        None => {
+            return Err(LinderaErrorKind::Io.with_error(anyhow::anyhow!(
+                "failed to get parent directory of output file"
+            )))
+        }
+    };
+    fs::create_dir_all(parent_dir)
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+    let mut wtr = io::BufWriter::new(
+        File::create(output_file)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
+    );
+    bincode::serialize_into(&mut wtr, user_dict)
+        .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
+    wtr.flush()
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+    write_file(
+        &user_right_id_path(output_file),
+        &build_right_ids(normalized_rows, false)?,
+    )
+}
+
+fn write_file(path: &Path, buffer: &[u8]) -> LinderaResult<()> {
+    let mut wtr = io::BufWriter::new(
+        File::create(path).map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
+    );
+    write(buffer, &mut wtr)?;
+    wtr.flush()
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))
 }
 
 fn write<W: Write>(buffer: &[u8], writer: &mut W) -> LinderaResult<()> {
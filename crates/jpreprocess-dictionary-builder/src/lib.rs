@@ -1,4 +1,11 @@
 mod build_dict;
 pub mod to_csv;
 pub mod ipadic_builder;
+pub mod compress;
+pub mod diff;
+pub mod lint;
+pub mod lite_builder;
+pub mod merge;
+pub mod query;
 pub mod serializer;
+pub mod unidic_builder;
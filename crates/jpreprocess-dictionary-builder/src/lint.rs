@@ -0,0 +1,303 @@
+use std::{collections::HashMap, fmt::Display, path::Path, str::FromStr};
+
+use jpreprocess_core::{error::JPreprocessErrorKind, word_entry::WordEntry, JPreprocessError};
+use lindera_core::{error::LinderaErrorKind, LinderaResult};
+use serde::Serialize;
+
+use crate::build_dict::{normalize_rows, DETAILED_USERDIC_FIELDS_NUM, SIMPLE_USERDIC_FIELDS_NUM};
+
+/// A problem found in a dictionary csv row.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintIssue {
+    /// 1-based line number in the csv file
+    pub line: u64,
+    pub surface: String,
+    pub kind: LintKind,
+    pub message: String,
+}
+
+impl Display for LintIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: {}: [{}] {}",
+            self.line, self.surface, self.kind, self.message
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintKind {
+    /// The row does not have enough columns.
+    FieldCount,
+    /// left_id, right_id or cost is not a number.
+    Cost,
+    PartOfSpeech,
+    CType,
+    CForm,
+    Pronunciation,
+    /// The accent is negative or exceeds the mora size.
+    Accent,
+    /// orig, read, pron and accent/mora_size have different number of `:`-separated items.
+    SplitCount,
+    /// The details cannot be loaded as a word entry for other reasons.
+    Entry,
+    /// Same surface and details as one of the preceding rows.
+    Duplicate,
+}
+
+impl Display for LintKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::FieldCount => "field_count",
+            Self::Cost => "cost",
+            Self::PartOfSpeech => "part_of_speech",
+            Self::CType => "ctype",
+            Self::CForm => "cform",
+            Self::Pronunciation => "pronunciation",
+            Self::Accent => "accent",
+            Self::SplitCount => "split_count",
+            Self::Entry => "entry",
+            Self::Duplicate => "duplicate",
+        })
+    }
+}
+
+/// Reads a dictionary csv and checks all rows.
+pub fn lint_csv(input_file: &Path) -> LinderaResult<Vec<LintIssue>> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(input_file)
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+    let mut lines = vec![];
+    let mut rows = vec![];
+    for result in rdr.records() {
+        let record =
+            result.map_err(|err| LinderaErrorKind::Content.with_error(anyhow::anyhow!(err)))?;
+        lines.push(record.position().map(|p| p.line()).unwrap_or(0));
+        rows.push(record);
+    }
+
+    let normalized_rows: Vec<Vec<String>> = normalize_rows(&rows);
+    Ok(lint_rows(lines.into_iter().zip(normalized_rows)))
+}
+
+/// Checks rows of a dictionary csv, each of which is paired with its line number.
+///
+/// The details of each row are loaded with [`WordEntry::load`], the same as building
+/// the dictionary, and its error is reported. The other checks are done on top of it.
+///
+/// Rows with 3 columns are regarded as simple user dictionary entries.
+pub fn lint_rows<I>(rows: I) -> Vec<LintIssue>
+where
+    I: IntoIterator<Item = (u64, Vec<String>)>,
+{
+    let mut issues = vec![];
+    let mut first_lines: HashMap<Vec<String>, u64> = HashMap::new();
+
+    for (line, row) in rows {
+        let mut issue = |kind: LintKind, message: String| {
+            issues.push(LintIssue {
+                line,
+                surface: row.first().cloned().unwrap_or_default(),
+                kind,
+                message,
+            })
+        };
+
+        if row.len() == SIMPLE_USERDIC_FIELDS_NUM {
+            continue;
+        }
+        if row.len() < DETAILED_USERDIC_FIELDS_NUM {
+            issue(
+                LintKind::FieldCount,
+                format!(
+                    "expected {} or {}+ fields, found {}",
+                    SIMPLE_USERDIC_FIELDS_NUM,
+                    DETAILED_USERDIC_FIELDS_NUM,
+                    row.len()
+                ),
+            );
+            continue;
+        }
+
+        if u16::from_str(row[1].trim()).is_err() || u16::from_str(row[2].trim()).is_err() {
+            issue(
+                LintKind::Cost,
+                format!("invalid context id {},{}", row[1], row[2]),
+            );
+        }
+        if i16::from_str(row[3].trim()).is_err() {
+            issue(LintKind::Cost, format!("invalid cost {}", row[3]));
+        }
+
+        let mut details: Vec<&str> = row[4..].iter().map(|s| s.as_str()).collect();
+        details.resize(13, "");
+
+        let counts: Vec<usize> = details[6..10]
+            .iter()
+            .map(|s| s.split(':').count())
+            .collect();
+        if counts.iter().any(|count| *count != counts[0]) {
+            issue(
+                LintKind::SplitCount,
+                format!(
+                    "orig, read, pron and accent have {}, {}, {} and {} items respectively",
+                    counts[0], counts[1], counts[2], counts[3]
+                ),
+            );
+        }
+
+        match WordEntry::load(&details) {
+            Ok(entry) => {
+                for (orig, word_details) in entry.get_with_string(&row[0]) {
+                    let (acc, mora_size) = (word_details.acc, word_details.mora_size);
+                    if acc < 0 || acc > mora_size {
+                        issue(
+                            LintKind::Accent,
+                            format!(
+                                "accent {} of {} is out of range of mora size {}",
+                                acc, orig, mora_size
+                            ),
+                        );
+                    }
+                }
+            }
+            Err(err) => {
+                let (kind, value) = match err.kind {
+                    JPreprocessErrorKind::PartOfSpeechParseError => {
+                        (LintKind::PartOfSpeech, details[0..4].join(","))
+                    }
+                    JPreprocessErrorKind::CTypeParseError => (LintKind::CType, details[4].into()),
+                    JPreprocessErrorKind::CFormParseError => (LintKind::CForm, details[5].into()),
+                    JPreprocessErrorKind::PronunciationParseError => {
+                        (LintKind::Pronunciation, details[8].into())
+                    }
+                    _ => (LintKind::Entry, details.join(",")),
+                };
+                issue(kind, describe(&value, err));
+            }
+        }
+
+        let key: Vec<String> = [&row[0..1], &row[4..]].concat();
+        match first_lines.get(&key) {
+            Some(first_line) => issue(
+                LintKind::Duplicate,
+                format!("same entry as line {}", first_line),
+            ),
+            None => {
+                first_lines.insert(key, line);
+            }
+        }
+    }
+
+    issues
+}
+
+fn describe(value: &str, err: JPreprocessError) -> String {
+    format!("{} ({:?})", value, err.kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lint_rows, LintKind};
+
+    fn lint(rows: &[&str]) -> Vec<(u64, LintKind)> {
+        lint_rows(
+            rows.iter()
+                .enumerate()
+                .map(|(i, row)| (i as u64 + 1, row.split(',').map(String::from).collect())),
+        )
+        .into_iter()
+        .map(|issue| (issue.line, issue.kind))
+        .collect()
+    }
+
+    #[test]
+    fn valid() {
+        assert!(lint(&[
+            "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2/3,C1",
+            "あーあ,3,3,10000,感動詞,*,*,*,*,*,あー:あ,アー:ア,アー:ア,1/2:1/1,C1,",
+            "クーバネティス,名詞,クーバネティス",
+            "．,9,9,2968,記号,句点,*,*,*,*,．,．,．,*/*,*",
+        ])
+        .is_empty());
+    }
+
+    #[test]
+    fn unknown_columns() {
+        assert_eq!(
+            lint(&[
+                "日本,1354,1354,10787,名詞,地名,地域,国,*,*,日本,ニホン,ニホン,2/3,C1",
+                "日本,1354,1354,10787,名詞,固有名詞,地域,国,八段,*,日本,ニホン,ニホン,2/3,C1",
+                "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,連体形,日本,ニホン,ニホン,2/3,C1",
+                "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニホン,ニxホン,2/3,C1",
+            ]),
+            [
+                (1, LintKind::PartOfSpeech),
+                (2, LintKind::CType),
+                (3, LintKind::CForm),
+                (4, LintKind::Pronunciation),
+            ]
+        );
+    }
+
+    #[test]
+    fn accent() {
+        assert_eq!(
+            lint(&[
+                "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,4/3,C1",
+                // read as 0/0 by the loader
+                "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2,C1",
+                "あーあ,3,3,10000,感動詞,*,*,*,*,*,あー:あ,アー:ア,アー:ア,1/2:3/1,C1,",
+            ]),
+            [(1, LintKind::Accent), (3, LintKind::Accent)]
+        );
+    }
+
+    #[test]
+    fn structure() {
+        assert_eq!(
+            lint(&[
+                "日本,1354,1354,10787,名詞,固有名詞,地域,国",
+                "日本,a,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2/3,C1",
+                "あーあ,3,3,10000,感動詞,*,*,*,*,*,あー:あ,アー:ア,アー,1/2:1/1,C1",
+                "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニ:ホン,ニホン,2/3,C1",
+            ]),
+            [
+                (1, LintKind::FieldCount),
+                (2, LintKind::Cost),
+                (3, LintKind::SplitCount),
+                (4, LintKind::SplitCount)
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicate() {
+        let issues = lint_rows(
+            [
+                (
+                    1,
+                    "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2/3,C1",
+                ),
+                (
+                    2,
+                    "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニッポン,ニッポン,3/4,C1",
+                ),
+                (
+                    3,
+                    "日本,1300,1300,8000,名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2/3,C1",
+                ),
+            ]
+            .map(|(line, row)| (line, row.split(',').map(String::from).collect())),
+        );
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 3);
+        assert_eq!(issues[0].kind, LintKind::Duplicate);
+        assert_eq!(issues[0].message, "same entry as line 1");
+    }
+}
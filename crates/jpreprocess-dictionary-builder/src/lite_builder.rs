@@ -0,0 +1,302 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt::Display,
+    fs,
+    path::Path,
+    str::FromStr,
+};
+
+use log::debug;
+
+use lindera_core::{
+    dictionary_builder::DictionaryBuilder, error::LinderaErrorKind, file_util::read_utf8_file,
+    LinderaResult,
+};
+
+use crate::{
+    ipadic_builder::{read_cost_matrix, read_dict_csv_rows, CostMatrix, IpadicBuilder},
+    serializer::DictionarySerializer,
+};
+
+/// Rules to choose the entries to be kept in a lite dictionary.
+#[derive(Debug, Clone, Default)]
+pub struct PruneConfig {
+    /// Drops the entries whose cost is higher than this, i.e. the less frequent ones.
+    pub max_cost: Option<i16>,
+    /// Drops the entries whose POS matches any of these, e.g. `名詞,固有名詞,人名`.
+    ///
+    /// The trailing groups may be omitted, and `*` matches any group.
+    pub deny_pos: Vec<String>,
+    /// Keeps the entries with these surfaces regardless of the other rules.
+    pub allow: HashSet<String>,
+    /// Drops the entries with these surfaces.
+    pub deny: HashSet<String>,
+    /// Merges the context ids which have the same connection costs,
+    /// and drops the ones no longer used, to shrink the connection matrix.
+    ///
+    /// This does not change the tokenization result.
+    pub merge_context_ids: bool,
+}
+
+impl PruneConfig {
+    /// Returns whether the normalized dictionary csv row should be kept.
+    pub fn keep(&self, row: &[String]) -> bool {
+        let surface = &row[0];
+        if self.allow.contains(surface) {
+            return true;
+        }
+        if self.deny.contains(surface) {
+            return false;
+        }
+
+        if let (Some(max_cost), Some(Ok(cost))) = (
+            self.max_cost,
+            row.get(3).map(|cost| i16::from_str(cost.trim())),
+        ) {
+            if cost > max_cost {
+                return false;
+            }
+        }
+
+        match row.get(4..8) {
+            Some(pos) => !self
+                .deny_pos
+                .iter()
+                .any(|pattern| pattern.split(',').zip(pos).all(|(p, g)| p == "*" || p == g)),
+            None => true,
+        }
+    }
+}
+
+/// The size of the dictionary before and after pruning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PruneSummary {
+    pub rows_before: usize,
+    pub rows_after: usize,
+    pub context_ids_before: usize,
+    pub context_ids_after: usize,
+}
+
+impl Display for PruneSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "entries: {} -> {}, context ids: {} -> {}",
+            self.rows_before, self.rows_after, self.context_ids_before, self.context_ids_after
+        )
+    }
+}
+
+/// Builds a small system dictionary from IPADIC/naist-jdic source,
+/// dropping the entries according to [`PruneConfig`].
+pub struct LiteBuilder {
+    ipadic_builder: IpadicBuilder,
+    config: PruneConfig,
+}
+
+impl LiteBuilder {
+    pub fn new(
+        serializer: Box<dyn Send + Sync + DictionarySerializer>,
+        config: PruneConfig,
+    ) -> Self {
+        Self {
+            ipadic_builder: IpadicBuilder::new(serializer),
+            config,
+        }
+    }
+
+    pub fn build_dictionary(
+        &self,
+        input_dir: &Path,
+        output_dir: &Path,
+    ) -> LinderaResult<PruneSummary> {
+        fs::create_dir_all(output_dir)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+        let chardef = self.ipadic_builder.build_chardef(input_dir, output_dir)?;
+
+        let unk_data_path = input_dir.join("unk.def");
+        debug!("reading {:?}", unk_data_path);
+        let mut unk_rows: Vec<Vec<String>> = read_utf8_file(&unk_data_path)?
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.split(',').map(str::to_string).collect())
+            .collect();
+
+        let rows = read_dict_csv_rows(input_dir)?;
+        let rows_before = rows.len();
+        let mut rows: Vec<Vec<String>> = rows
+            .into_iter()
+            .filter(|row| self.config.keep(row))
+            .collect();
+
+        let mut matrix = read_cost_matrix(input_dir)?;
+        let context_ids_before = matrix.forward_size as usize;
+
+        if self.config.merge_context_ids {
+            // id 0 is used for BOS/EOS
+            let mut used = BTreeSet::from([0]);
+            for row in rows.iter().chain(&unk_rows) {
+                used.insert(parse_context_id(row, 1)?);
+                used.insert(parse_context_id(row, 2)?);
+            }
+
+            let (id_map, merged) = merge_context_ids(&matrix, &used)?;
+            for row in rows.iter_mut().chain(&mut unk_rows) {
+                for column in [1, 2] {
+                    row[column] = id_map[&parse_context_id(row, column)?].to_string();
+                }
+            }
+            matrix = merged;
+        }
+
+        let unk_data = unk_rows
+            .iter()
+            .map(|row| row.join(","))
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.ipadic_builder
+            .build_unk_from_data(&unk_data, &chardef, output_dir)?;
+
+        let rows_after = rows.len();
+        self.ipadic_builder.build_dict_from_rows(rows, output_dir)?;
+        matrix.write(output_dir)?;
+
+        Ok(PruneSummary {
+            rows_before,
+            rows_after,
+            context_ids_before,
+            context_ids_after: matrix.forward_size as usize,
+        })
+    }
+}
+
+fn parse_context_id(row: &[String], column: usize) -> LinderaResult<u16> {
+    row.get(column)
+        .and_then(|id| u16::from_str(id.trim()).ok())
+        .ok_or_else(|| {
+            LinderaErrorKind::Parse.with_error(anyhow::anyhow!(
+                "failed to parse context id of {}",
+                row.join(",")
+            ))
+        })
+}
+
+/// Maps the `used` context ids to new ids, so that the ids with the same connection costs
+/// (both as the left context and the right context) share the same id.
+///
+/// Lindera uses left_id for both sides, so the new matrix is square.
+/// The order of the ids is preserved, so id 0 is always mapped to 0.
+fn merge_context_ids(
+    matrix: &CostMatrix,
+    used: &BTreeSet<u16>,
+) -> LinderaResult<(BTreeMap<u16, u16>, CostMatrix)> {
+    if let Some(id) = used
+        .iter()
+        .find(|id| **id as u32 >= matrix.forward_size || **id as u32 >= matrix.backward_size)
+    {
+        return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+            "context id {} is out of range of matrix.def",
+            id
+        )));
+    }
+
+    let mut classes: HashMap<Vec<i16>, u16> = HashMap::new();
+    let mut representatives: Vec<u16> = vec![];
+    let mut id_map = BTreeMap::new();
+    for id in used {
+        let costs: Vec<i16> = used
+            .iter()
+            .flat_map(|other| [matrix.cost(*id, *other), matrix.cost(*other, *id)])
+            .collect();
+        let new_id = *classes.entry(costs).or_insert_with(|| {
+            representatives.push(*id);
+            representatives.len() as u16 - 1
+        });
+        id_map.insert(*id, new_id);
+    }
+
+    let size = representatives.len() as u32;
+    let costs = representatives
+        .iter()
+        .flat_map(|forward_id| {
+            representatives
+                .iter()
+                .map(|backward_id| matrix.cost(*forward_id, *backward_id))
+        })
+        .collect();
+
+    Ok((
+        id_map,
+        CostMatrix {
+            forward_size: size,
+            backward_size: size,
+            costs,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeSet, HashSet};
+
+    use crate::ipadic_builder::CostMatrix;
+
+    use super::{merge_context_ids, PruneConfig};
+
+    fn row(s: &str) -> Vec<String> {
+        s.split(',').map(String::from).collect()
+    }
+
+    #[test]
+    fn keep() {
+        let config = PruneConfig {
+            max_cost: Some(5000),
+            deny_pos: vec!["名詞,固有名詞,人名".to_string()],
+            allow: HashSet::from(["山田".to_string()]),
+            deny: HashSet::from(["合成".to_string()]),
+            merge_context_ids: false,
+        };
+
+        assert!(config.keep(&row(
+            "文,1345,1345,4000,名詞,一般,*,*,*,*,文,ブン,ブン,1/2,C3"
+        )));
+        assert!(!config.keep(&row(
+            "文,1345,1345,6285,名詞,一般,*,*,*,*,文,ブン,ブン,1/2,C3"
+        )));
+        assert!(!config.keep(&row(
+            "合成,1343,1343,4329,名詞,サ変接続,*,*,*,*,合成,ゴウセイ,ゴーセー,0/4,C2"
+        )));
+        assert!(!config.keep(&row(
+            "太郎,1291,1291,3000,名詞,固有名詞,人名,名,*,*,太郎,タロウ,タロー,1/3,C1"
+        )));
+        assert!(config.keep(&row(
+            "山田,1290,1290,8000,名詞,固有名詞,人名,姓,*,*,山田,ヤマダ,ヤマダ,0/3,C1"
+        )));
+    }
+
+    #[test]
+    fn merge() {
+        // ids 1 and 2 have the same costs, and id 3 is not used.
+        #[rustfmt::skip]
+        let matrix = CostMatrix {
+            forward_size: 4,
+            backward_size: 4,
+            costs: vec![
+                0, 10, 10, 1,
+                20, 5, 5, 2,
+                20, 5, 5, 3,
+                4, 4, 4, 4,
+            ],
+        };
+        let (id_map, merged) = merge_context_ids(&matrix, &BTreeSet::from([0, 1, 2])).unwrap();
+        assert_eq!(
+            id_map.into_iter().collect::<Vec<_>>(),
+            [(0, 0), (1, 1), (2, 1)]
+        );
+        assert_eq!(merged.forward_size, 2);
+        assert_eq!(merged.costs, [0, 10, 20, 5]);
+
+        assert!(merge_context_ids(&matrix, &BTreeSet::from([0, 4])).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::HashSet;
+
+use crate::build_dict::entry_key;
+
+/// Applies `overrides` to `base` in order.
+///
+/// All rows in `base` sharing the [surface and details](crate::build_dict::entry_key)
+/// of a row in an override are replaced with the rows in that override.
+/// Rows of new words are appended.
+pub fn merge_rows(base: Vec<Vec<String>>, overrides: &[Vec<Vec<String>>]) -> Vec<Vec<String>> {
+    overrides.iter().fold(base, |mut rows, override_rows| {
+        let keys: HashSet<Vec<String>> = override_rows.iter().map(|row| entry_key(row)).collect();
+        rows.retain(|row| !keys.contains(&entry_key(row)));
+        rows.extend(override_rows.iter().cloned());
+        rows
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge_rows;
+
+    fn rows(rows: &[&str]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.split(',').map(String::from).collect())
+            .collect()
+    }
+
+    #[test]
+    fn merge() {
+        let base = rows(&[
+            "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2/3,C1",
+            "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニッポン,ニッポン,3/4,C1",
+            "東京,1354,1354,3000,名詞,固有名詞,地域,一般,*,*,東京,トウキョウ,トーキョー,0/4,C1",
+        ]);
+        let first = rows(&[
+            "日本,1354,1354,9000,名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2/3,C1",
+            "大阪,1354,1354,3000,名詞,固有名詞,地域,一般,*,*,大阪,オオサカ,オーサカ,0/4,C1",
+        ]);
+        let second = rows(&["大阪,名詞,オオサカ"]);
+
+        assert_eq!(
+            merge_rows(base.clone(), &[first.clone(), second.clone()]),
+            [
+                base[2].clone(),
+                first[0].clone(),
+                first[1].clone(),
+                second[0].clone(),
+            ]
+        );
+    }
+}
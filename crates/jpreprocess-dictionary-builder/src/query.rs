@@ -0,0 +1,192 @@
+use std::fmt::Display;
+
+use byteorder::{ByteOrder, LittleEndian};
+use lindera_core::{prefix_dict::PrefixDict, word_entry::WordEntry, LinderaResult};
+
+use crate::{serializer::DictionarySerializer, to_csv::da::DoubleArrayParser};
+
+/// An entry found by [`EntryLookup`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LookupEntry {
+    pub surface: String,
+    pub word_entry: WordEntry,
+    /// The details, in the same format as the dictionary csv
+    pub details: String,
+}
+
+impl Display for LookupEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "word_id: {}, context_id: {}, cost: {}\t{},{}",
+            self.word_entry.word_id.0,
+            self.word_entry.cost_id,
+            self.word_entry.word_cost,
+            self.surface,
+            self.details
+        )
+    }
+}
+
+/// Looks up entries of a built dictionary.
+pub struct EntryLookup<'a> {
+    prefix_dict: &'a PrefixDict,
+    words_idx_data: &'a [u8],
+    words_data: &'a [u8],
+    serializer: &'a dyn DictionarySerializer,
+}
+
+impl<'a> EntryLookup<'a> {
+    pub fn new(
+        prefix_dict: &'a PrefixDict,
+        words_idx_data: &'a [u8],
+        words_data: &'a [u8],
+        serializer: &'a dyn DictionarySerializer,
+    ) -> Self {
+        Self {
+            prefix_dict,
+            words_idx_data,
+            words_data,
+            serializer,
+        }
+    }
+
+    /// Returns all entries whose surface is `surface`.
+    pub fn surface(&self, surface: &str) -> LinderaResult<Vec<LookupEntry>> {
+        match self.prefix_dict.da.exact_match_search(surface) {
+            Some(offset_len) => self.entries(surface, offset_len),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Returns all entries whose surface starts with `prefix`, ordered by surface.
+    pub fn prefix(&self, prefix: &str) -> LinderaResult<Vec<LookupEntry>> {
+        let mut keyset = DoubleArrayParser(&self.prefix_dict.da.0).inverse_da();
+        keyset.retain(|(surface, _)| surface.starts_with(prefix));
+        keyset.sort();
+        self.keyset_entries(keyset)
+    }
+
+    /// Returns all entries whose reading or pronunciation is `reading`.
+    ///
+    /// Hiragana is regarded as katakana, and `:` (separator of compound words)
+    /// and `’` (unvoiced mark) are ignored.
+    pub fn reading(&self, reading: &str) -> LinderaResult<Vec<LookupEntry>> {
+        let reading = normalize_reading(reading);
+
+        let mut keyset = DoubleArrayParser(&self.prefix_dict.da.0).inverse_da();
+        keyset.sort();
+
+        let mut result = self.keyset_entries(keyset)?;
+        result.retain(|entry| {
+            let details: Vec<&str> = entry.details.split(',').collect();
+            // details[7]: read, details[8]: pron
+            details
+                .iter()
+                .skip(7)
+                .take(2)
+                .any(|column| normalize_reading(column) == reading)
+        });
+        Ok(result)
+    }
+
+    fn keyset_entries(&self, keyset: Vec<(String, u32)>) -> LinderaResult<Vec<LookupEntry>> {
+        let mut result = vec![];
+        for (surface, offset_len) in keyset {
+            result.extend(self.entries(&surface, offset_len)?);
+        }
+        Ok(result)
+    }
+
+    fn entries(&self, surface: &str, offset_len: u32) -> LinderaResult<Vec<LookupEntry>> {
+        let len = offset_len & 0x1f;
+        let offset = offset_len >> 5;
+        let offset_bytes = (offset as usize) * WordEntry::SERIALIZED_LEN;
+        let data: &[u8] = &self.prefix_dict.vals_data[offset_bytes..];
+
+        (0..len as usize)
+            .map(|i| {
+                let word_entry = WordEntry::deserialize(
+                    &data[WordEntry::SERIALIZED_LEN * i..],
+                    self.prefix_dict.is_system,
+                );
+                let word_id = word_entry.word_id.0 as usize;
+                let idx =
+                    LittleEndian::read_u32(&self.words_idx_data[word_id * 4..(word_id + 1) * 4])
+                        as usize;
+                let details = self
+                    .serializer
+                    .deserialize(&self.words_data[idx..], surface.to_string())?;
+
+                Ok(LookupEntry {
+                    surface: surface.to_string(),
+                    word_entry,
+                    details,
+                })
+            })
+            .collect()
+    }
+}
+
+fn normalize_reading(reading: &str) -> String {
+    reading
+        .chars()
+        .filter(|c| !matches!(c, ':' | '’'))
+        .map(|c| match c {
+            'ぁ'..='ゖ' => char::from_u32(c as u32 + 0x60).unwrap(),
+            _ => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use crate::{ipadic_builder::IpadicBuilder, serializer::LinderaSerializer};
+
+    use super::EntryLookup;
+
+    #[test]
+    fn lookup() -> Result<(), Box<dyn Error>> {
+        let rows = [
+            "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2/3,C1",
+            "日本,1354,1354,10787,名詞,固有名詞,地域,国,*,*,日本,ニッポン,ニッポン,3/4,C1",
+            "日本語,1285,1285,5000,名詞,一般,*,*,*,*,日本語,ニホンゴ,ニホンゴ,0/4,C1",
+            "二本,1300,1300,8000,名詞,数,*,*,*,*,二本,ニホン,ニホン,1/3,C1",
+            "本,1285,1285,6000,名詞,一般,*,*,*,*,本,ホン,ホン,1/2,C1",
+        ];
+        let rows_split: Vec<Vec<&str>> = rows.map(|s| s.split(',').collect()).to_vec();
+
+        let builder = IpadicBuilder::new(Box::new(LinderaSerializer));
+        let user_dict = builder.build_user_dict_from_data(&rows_split)?;
+        let lookup = EntryLookup::new(
+            &user_dict.dict,
+            &user_dict.words_idx_data,
+            &user_dict.words_data,
+            &LinderaSerializer,
+        );
+
+        let to_rows = |entries: Vec<super::LookupEntry>| -> Vec<String> {
+            entries
+                .into_iter()
+                .map(|entry| {
+                    format!(
+                        "{},{},{},{},{}",
+                        entry.surface,
+                        entry.word_entry.cost_id,
+                        entry.word_entry.cost_id,
+                        entry.word_entry.word_cost,
+                        entry.details
+                    )
+                })
+                .collect()
+        };
+
+        assert_eq!(to_rows(lookup.surface("日本")?), [rows[0], rows[1]]);
+        assert!(lookup.surface("日")?.is_empty());
+        assert_eq!(to_rows(lookup.prefix("日本")?), [rows[0], rows[1], rows[2]]);
+        assert_eq!(to_rows(lookup.reading("にほん")?), [rows[3], rows[0]]);
+        Ok(())
+    }
+}
@@ -50,17 +50,21 @@ impl DictionarySerializer for JPreprocessSerializer {
         let mut str_details = row.iter().map(|d| &d[..]).collect::<Vec<&str>>();
         str_details.resize(13, "");
         match WordEntry::load(&str_details[..]) {
-            Ok(entry) => bincode::serialize(&entry)
+            Ok(entry) => entry
+                .to_bytes()
                 .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err))),
             Err(err) => {
-                eprintln!("ERR: jpreprocess parse failed. Word:\n{:?}", &row);
+                eprintln!(
+                    "ERR: jpreprocess parse failed. Run `dict_tools lint` to list all problems. Word:\n{:?}",
+                    &row
+                );
                 Err(LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))
             }
         }
     }
     fn deserialize(&self, data: &[u8], string: String) -> LinderaResult<String> {
         use jpreprocess_core::word_entry::WordEntry;
-        let word_entry: WordEntry = bincode::deserialize(data)
+        let word_entry = WordEntry::from_bytes(data)
             .map_err(|err| LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!(err)))?;
         Ok(word_entry.to_str_vec(string).join(","))
     }
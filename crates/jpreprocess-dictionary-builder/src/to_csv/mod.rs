@@ -5,17 +5,33 @@ use std::collections::BTreeMap;
 
 use self::da::DoubleArrayParser;
 
-mod da;
+pub(crate) mod da;
 
 /// Converts dictionary to csv.
 ///
 /// The third column (right_id) cannot be recovered
 /// because it is already lost while building the dictionary.
+/// Use [`dict_to_csv_with_right_ids`] if the right_id table is available.
 pub fn dict_to_csv(
     prefix_dict: &PrefixDict,
     words_idx_data: &[u8],
     words_data: &[u8],
     serializer: &dyn DictionarySerializer,
+) -> LinderaResult<Vec<String>> {
+    dict_to_csv_with_right_ids(prefix_dict, words_idx_data, words_data, None, serializer)
+}
+
+/// Converts dictionary to csv, restoring right_id from the table written by
+/// [`IpadicBuilder`](crate::ipadic_builder::IpadicBuilder).
+///
+/// If `right_id_data` is `None` or does not contain the word,
+/// right_id is assumed to be the same as left_id.
+pub fn dict_to_csv_with_right_ids(
+    prefix_dict: &PrefixDict,
+    words_idx_data: &[u8],
+    words_data: &[u8],
+    right_id_data: Option<&[u8]>,
+    serializer: &dyn DictionarySerializer,
 ) -> LinderaResult<Vec<String>> {
     let word_entry_map = inverse_prefix_dict(prefix_dict, true);
 
@@ -32,14 +48,13 @@ pub fn dict_to_csv(
 
     Ok(rows
         .into_iter()
-        .zip(words_to_csv(words_idx_data, words_data, words, serializer)?.into_iter())
+        .zip(words_to_csv(words_idx_data, words_data, words, serializer)?)
         .map(|((string, word_entry), right)| {
             format!(
                 "{},{},{},{},{}",
                 string,
                 word_entry.cost_id,
-                // Lindera does not use right_id, so assuming that it is same as the left_id
-                word_entry.cost_id,
+                right_id(right_id_data, &word_entry),
                 word_entry.word_cost,
                 right
             )
@@ -47,6 +62,15 @@ pub fn dict_to_csv(
         .collect())
 }
 
+fn right_id(right_id_data: Option<&[u8]>, word_entry: &WordEntry) -> u16 {
+    let index = word_entry.word_id.0 as usize * 2;
+    match right_id_data.and_then(|data| data.get(index..index + 2)) {
+        Some(bytes) => LittleEndian::read_u16(bytes),
+        // Lindera does not use right_id, so assuming that it is same as the left_id
+        None => word_entry.cost_id,
+    }
+}
+
 /// Converts prefix dict to WordEntry map.
 ///
 /// This is considered to be inverse of build_prefix_dict,
@@ -99,9 +123,12 @@ pub fn words_to_csv(
 mod tests {
     use std::error::Error;
 
-    use crate::{ipadic_builder::IpadicBuilder, serializer::LinderaSerializer};
+    use crate::{
+        ipadic_builder::{user_right_id_path, IpadicBuilder},
+        serializer::LinderaSerializer,
+    };
 
-    use super::dict_to_csv;
+    use super::{dict_to_csv, dict_to_csv_with_right_ids};
 
     #[test]
     fn inverse() -> Result<(), Box<dyn Error>> {
@@ -128,4 +155,42 @@ mod tests {
         assert_eq!(inverse[2], rows[1]);
         Ok(())
     }
+
+    #[test]
+    fn right_id() -> Result<(), Box<dyn Error>> {
+        let rows = [
+            "あー,100,200,5000,フィラー,*,*,*,*,*,あー,アー,アー,1/2,*,-1",
+            "日本,1354,1355,10787,名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2/3,C1,-1",
+            "クーバネティス,名詞,クーバネティス",
+        ];
+        let rows_split: Vec<Vec<String>> = rows
+            .map(|s| s.split(',').map(String::from).collect())
+            .to_vec();
+
+        let dir = std::env::temp_dir().join(format!("jpreprocess-right-id-{}", std::process::id()));
+        let dict_path = dir.join("user.bin");
+        let builder = IpadicBuilder::new(Box::new(LinderaSerializer));
+        builder.build_user_dictionary_from_rows(&rows_split, &dict_path)?;
+
+        let user_dict: lindera_core::dictionary::UserDictionary =
+            bincode::deserialize(&std::fs::read(&dict_path)?)?;
+        let right_id_data = std::fs::read(user_right_id_path(&dict_path))?;
+        std::fs::remove_dir_all(&dir)?;
+
+        let inverse = dict_to_csv_with_right_ids(
+            &user_dict.dict,
+            &user_dict.words_idx_data,
+            &user_dict.words_data,
+            Some(&right_id_data),
+            &LinderaSerializer,
+        )?;
+
+        assert_eq!(inverse[0], rows[0]);
+        assert_eq!(inverse[2], rows[1]);
+        assert_eq!(
+            inverse[1],
+            "クーバネティス,0,0,-10000,名詞,*,*,*,*,*,クーバネティス,クーバネティス,*"
+        );
+        Ok(())
+    }
 }
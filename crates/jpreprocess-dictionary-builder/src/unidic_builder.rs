@@ -0,0 +1,479 @@
+//! Builds jpreprocess dictionary from UniDic.
+//!
+//! Each UniDic row is converted to the IPADIC (naist-jdic) layout,
+//! and then built with [`IpadicBuilder`].
+
+use std::{path::Path, str::FromStr};
+
+use log::debug;
+
+use lindera_core::{
+    character_definition::CharacterDefinitions, dictionary::UserDictionary,
+    dictionary_builder::DictionaryBuilder, file_util::read_utf8_file, LinderaResult,
+};
+
+use jpreprocess_core::{
+    accent_rule::{AccentType, POSMatch},
+    pronunciation::Pronunciation,
+};
+
+use crate::{
+    build_dict::SIMPLE_USERDIC_FIELDS_NUM,
+    ipadic_builder::{read_csv_rows, read_dict_csv_rows, sort_rows, IpadicBuilder},
+    serializer::DictionarySerializer,
+};
+
+/// Column indices of UniDic csv.
+///
+/// The default is the layout of unidic-cwj and unidic-csj 3.x.
+#[derive(Clone, Debug)]
+pub struct UnidicColumns {
+    /// The first of the four POS columns (pos1-pos4)
+    pub pos: usize,
+    pub ctype: usize,
+    pub cform: usize,
+    pub pron: usize,
+    pub orth_base: usize,
+    pub kana: usize,
+    pub a_type: usize,
+    pub a_con_type: usize,
+}
+
+impl Default for UnidicColumns {
+    fn default() -> Self {
+        Self {
+            pos: 4,
+            ctype: 8,
+            cform: 9,
+            pron: 13,
+            orth_base: 14,
+            kana: 24,
+            a_type: 28,
+            a_con_type: 29,
+        }
+    }
+}
+
+pub struct UnidicBuilder {
+    ipadic_builder: IpadicBuilder,
+    columns: UnidicColumns,
+}
+
+impl UnidicBuilder {
+    pub fn new(serializer: Box<dyn Send + Sync + DictionarySerializer>) -> Self {
+        Self {
+            ipadic_builder: IpadicBuilder::new(serializer),
+            columns: UnidicColumns::default(),
+        }
+    }
+
+    /// Uses `columns` instead of the unidic-cwj 3.x layout.
+    pub fn with_columns(mut self, columns: UnidicColumns) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Converts a UniDic row to IPADIC layout.
+    ///
+    /// Rows of simple user dictionary (surface, POS, reading) are left as is.
+    pub fn convert_row(&self, row: &[String]) -> Vec<String> {
+        if row.len() == SIMPLE_USERDIC_FIELDS_NUM {
+            return row.to_vec();
+        }
+
+        let column = |index: usize| match row.get(index).map(|s| s.as_str()) {
+            None | Some("") => "*",
+            Some(value) => value,
+        };
+        let c = &self.columns;
+
+        let surface = column(0);
+        let pos = convert_pos(
+            column(c.pos),
+            column(c.pos + 1),
+            column(c.pos + 2),
+            column(c.pos + 3),
+        );
+        let ctype = convert_ctype(column(c.ctype));
+        let cform = convert_cform(column(c.cform));
+
+        let (pron, mora_size) = [column(c.pron), column(c.kana), surface]
+            .into_iter()
+            .filter(|pron| *pron != "*")
+            .find_map(|pron| {
+                Pronunciation::from_str(pron)
+                    .ok()
+                    .map(|parsed| (pron, parsed.mora_size()))
+            })
+            .unwrap_or(("*", 0));
+        let acc = column(c.a_type)
+            .split(',')
+            .next()
+            .and_then(|acc| acc.parse::<usize>().ok())
+            .unwrap_or(0)
+            .min(mora_size);
+
+        let mut result: Vec<String> = row.iter().take(4).cloned().collect();
+        result.extend(pos.iter().map(|s| s.to_string()));
+        result.extend([
+            ctype.to_string(),
+            cform.to_string(),
+            column(c.orth_base).to_string(),
+            column(c.kana).to_string(),
+            pron.to_string(),
+            format!("{}/{}", acc, mora_size),
+            convert_chain_rule(column(c.a_con_type)),
+        ]);
+        result
+    }
+
+    fn convert_rows(&self, rows: Vec<Vec<String>>) -> Vec<Vec<String>> {
+        rows.iter().map(|row| self.convert_row(row)).collect()
+    }
+
+    /// Converts unk.def so that it has the same number of columns as IPADIC.
+    ///
+    /// Only the first four columns (category, left_id, right_id and cost) are used.
+    fn convert_unk(unk_data: &str) -> String {
+        unk_data
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let mut fields: Vec<&str> = line.split(',').take(4).collect();
+                fields.resize(IpadicBuilder::UNK_FIELDS_NUM, "*");
+                fields.join(",")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl DictionaryBuilder for UnidicBuilder {
+    fn build_dictionary(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
+        std::fs::create_dir_all(output_dir).map_err(|err| {
+            lindera_core::error::LinderaErrorKind::Io.with_error(anyhow::anyhow!(err))
+        })?;
+
+        let chardef = self.build_chardef(input_dir, output_dir)?;
+        self.build_unk(input_dir, &chardef, output_dir)?;
+        self.build_dict(input_dir, output_dir)?;
+        self.build_cost_matrix(input_dir, output_dir)?;
+
+        Ok(())
+    }
+
+    fn build_user_dictionary(&self, input_file: &Path, output_file: &Path) -> LinderaResult<()> {
+        let rows = self.convert_rows(read_csv_rows(input_file)?);
+        self.ipadic_builder
+            .build_user_dictionary_from_rows(&rows, output_file)
+    }
+
+    fn build_chardef(
+        &self,
+        input_dir: &Path,
+        output_dir: &Path,
+    ) -> LinderaResult<CharacterDefinitions> {
+        self.ipadic_builder.build_chardef(input_dir, output_dir)
+    }
+
+    fn build_unk(
+        &self,
+        input_dir: &Path,
+        chardef: &CharacterDefinitions,
+        output_dir: &Path,
+    ) -> LinderaResult<()> {
+        let unk_data_path = input_dir.join("unk.def");
+        debug!("reading {:?}", unk_data_path);
+
+        let unk_data = Self::convert_unk(&read_utf8_file(&unk_data_path)?);
+        self.ipadic_builder
+            .build_unk_from_data(&unk_data, chardef, output_dir)
+    }
+
+    fn build_dict(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
+        let rows = self.convert_rows(read_dict_csv_rows(input_dir)?);
+        self.ipadic_builder.build_dict_from_rows(rows, output_dir)
+    }
+
+    fn build_cost_matrix(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
+        self.ipadic_builder.build_cost_matrix(input_dir, output_dir)
+    }
+
+    fn build_user_dict(&self, input_file: &Path) -> LinderaResult<UserDictionary> {
+        let rows = self.convert_rows(read_csv_rows(input_file)?);
+        self.ipadic_builder
+            .build_user_dict_from_sorted_rows(&sort_rows(rows))
+    }
+}
+
+/// Converts UniDic POS to the nearest IPADIC POS.
+fn convert_pos<'a>(pos1: &'a str, pos2: &'a str, pos3: &'a str, pos4: &'a str) -> [&'a str; 4] {
+    match (pos1, pos2, pos3, pos4) {
+        ("名詞", "普通名詞", "サ変可能" | "サ変形状詞可能", _) => {
+            ["名詞", "サ変接続", "*", "*"]
+        }
+        ("名詞", "普通名詞", "形状詞可能", _) => ["名詞", "形容動詞語幹", "*", "*"],
+        ("名詞", "普通名詞", "副詞可能", _) => ["名詞", "副詞可能", "*", "*"],
+        ("名詞", "普通名詞", _, _) => ["名詞", "一般", "*", "*"],
+        ("名詞", "固有名詞", "人名", "姓" | "名") => ["名詞", "固有名詞", "人名", pos4],
+        ("名詞", "固有名詞", "人名", _) => ["名詞", "固有名詞", "人名", "一般"],
+        ("名詞", "固有名詞", "地名", "国") => ["名詞", "固有名詞", "地域", "国"],
+        ("名詞", "固有名詞", "地名", _) => ["名詞", "固有名詞", "地域", "一般"],
+        ("名詞", "固有名詞", _, _) => ["名詞", "固有名詞", "一般", "*"],
+        ("名詞", "数詞", _, _) => ["名詞", "数", "*", "*"],
+        ("名詞", "助動詞語幹", _, _) => ["名詞", "特殊", "助動詞語幹", "*"],
+        ("名詞", _, _, _) => ["名詞", "一般", "*", "*"],
+        ("代名詞", _, _, _) => ["名詞", "代名詞", "一般", "*"],
+        ("形状詞", "助動詞語幹", _, _) => ["名詞", "特殊", "助動詞語幹", "*"],
+        ("形状詞", _, _, _) => ["名詞", "形容動詞語幹", "*", "*"],
+        ("連体詞", _, _, _) => ["連体詞", "*", "*", "*"],
+        ("副詞", _, _, _) => ["副詞", "一般", "*", "*"],
+        ("接続詞", _, _, _) => ["接続詞", "*", "*", "*"],
+        ("感動詞", "フィラー", _, _) => ["フィラー", "*", "*", "*"],
+        ("感動詞", _, _, _) => ["感動詞", "*", "*", "*"],
+        ("動詞", "非自立可能", _, _) => ["動詞", "非自立", "*", "*"],
+        ("動詞", _, _, _) => ["動詞", "自立", "*", "*"],
+        ("形容詞", "非自立可能", _, _) => ["形容詞", "非自立", "*", "*"],
+        ("形容詞", _, _, _) => ["形容詞", "自立", "*", "*"],
+        ("助動詞", _, _, _) => ["助動詞", "*", "*", "*"],
+        ("助詞", "格助詞", _, _) => ["助詞", "格助詞", "一般", "*"],
+        ("助詞", "副助詞", _, _) => ["助詞", "副助詞", "*", "*"],
+        ("助詞", "係助詞", _, _) => ["助詞", "係助詞", "*", "*"],
+        ("助詞", "接続助詞", _, _) => ["助詞", "接続助詞", "*", "*"],
+        ("助詞", "終助詞", _, _) => ["助詞", "終助詞", "*", "*"],
+        ("助詞", "準体助詞", _, _) => ["名詞", "非自立", "一般", "*"],
+        ("助詞", _, _, _) => ["助詞", "特殊", "*", "*"],
+        ("接頭辞", _, _, _) => ["接頭詞", "名詞接続", "*", "*"],
+        ("接尾辞", "名詞的", "助数詞", _) => ["名詞", "接尾", "助数詞", "*"],
+        ("接尾辞", "名詞的", "サ変可能", _) => ["名詞", "接尾", "サ変接続", "*"],
+        ("接尾辞", "名詞的", "副詞可能", _) => ["名詞", "接尾", "副詞可能", "*"],
+        ("接尾辞", "形状詞的", _, _) => ["名詞", "接尾", "形容動詞語幹", "*"],
+        ("接尾辞", "動詞的", _, _) => ["動詞", "接尾", "*", "*"],
+        ("接尾辞", "形容詞的", _, _) => ["形容詞", "接尾", "*", "*"],
+        ("接尾辞", _, _, _) => ["名詞", "接尾", "一般", "*"],
+        ("記号", "文字", _, _) => ["記号", "アルファベット", "*", "*"],
+        ("補助記号", "句点", _, _) => ["記号", "句点", "*", "*"],
+        ("補助記号", "読点", _, _) => ["記号", "読点", "*", "*"],
+        ("補助記号", "括弧開", _, _) => ["記号", "括弧開", "*", "*"],
+        ("補助記号", "括弧閉", _, _) => ["記号", "括弧閉", "*", "*"],
+        ("記号" | "補助記号", _, _, _) => ["記号", "一般", "*", "*"],
+        ("空白", _, _, _) => ["記号", "空白", "*", "*"],
+        _ => ["その他", "*", "*", "*"],
+    }
+}
+
+/// Converts UniDic conjugation type (e.g. `五段-カ行`) to IPADIC one (e.g. `五段・カ行イ音便`).
+///
+/// UniDic does not split the adjectives into `アウオ段` and `イ段` as IPADIC does,
+/// so all of them are regarded as `形容詞・アウオ段`. jpreprocess does not distinguish them.
+/// The classical adjectives (`文語形容詞-ク`, `文語形容詞-シク`) have no IPADIC counterpart.
+fn convert_ctype(ctype: &str) -> String {
+    let (major, minor) = ctype.split_once('-').unwrap_or((ctype, ""));
+    match (major, minor) {
+        ("五段", "カ行") => "五段・カ行イ音便".to_string(),
+        ("五段", "ワア行") => "五段・ワ行促音便".to_string(),
+        ("五段", "ガ行" | "サ行" | "タ行" | "ナ行" | "バ行" | "マ行" | "ラ行") => {
+            format!("五段・{}", minor)
+        }
+        ("上一段" | "下一段", _) => "一段".to_string(),
+        ("カ行変格", _) => "カ変・来ル".to_string(),
+        ("サ行変格", _) => "サ変・スル".to_string(),
+        ("形容詞", _) => "形容詞・アウオ段".to_string(),
+        (
+            "助動詞",
+            "タ" | "ダ" | "デス" | "マス" | "ナイ" | "タイ" | "ヌ" | "ヤ" | "ジャ" | "ドス",
+        ) => format!("特殊・{}", minor),
+        ("無変化型", _) => "不変化型".to_string(),
+        _ => "*".to_string(),
+    }
+}
+
+/// Converts UniDic conjugation form (e.g. `連用形-促音便`) to IPADIC one (e.g. `連用タ接続`).
+///
+/// `語幹` (stem) has no IPADIC counterpart.
+fn convert_cform(cform: &str) -> &'static str {
+    let (major, minor) = cform.split_once('-').unwrap_or((cform, ""));
+    match (major, minor) {
+        ("終止形" | "連体形", _) => "基本形",
+        ("連用形", "促音便" | "撥音便" | "イ音便") => "連用タ接続",
+        ("連用形", "ウ音便") => "連用ゴザイ接続",
+        ("連用形", _) => "連用形",
+        ("未然形", _) => "未然形",
+        ("意志推量形", _) => "未然ウ接続",
+        ("仮定形" | "已然形", _) => "仮定形",
+        ("命令形", _) => "命令ｅ",
+        _ => "*",
+    }
+}
+
+/// Converts UniDic aConType (e.g. `動詞%F2@0,形容詞%F2@-1`) to jpreprocess chain rule.
+///
+/// Rules with accent types or POS jpreprocess does not support are dropped.
+fn convert_chain_rule(a_con_type: &str) -> String {
+    let rules: Vec<&str> = a_con_type
+        .split(',')
+        .filter(|rule| {
+            let (pos, rule) = rule.split_once('%').unwrap_or(("*", rule));
+            let accent_type = rule.split_once('@').map_or(rule, |(accent, _)| accent);
+            !accent_type.is_empty()
+                && POSMatch::from_str(pos).is_ok()
+                && matches!(AccentType::from_str(accent_type), Ok(t) if t != AccentType::None)
+        })
+        .collect();
+
+    if rules.is_empty() {
+        "*".to_string()
+    } else {
+        rules.join("/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jpreprocess_core::{
+        accent_rule::AccentType,
+        cform::CForm,
+        ctype::{CType, Keiyoushi},
+        pos::{Doushi, Joshi, KakuJoshi, KoyuMeishi, Meishi, Region, POS},
+        word_entry::WordEntry,
+    };
+
+    use crate::serializer::JPreprocessSerializer;
+
+    use super::{convert_chain_rule, UnidicBuilder};
+
+    fn convert(row: &str) -> Vec<String> {
+        let record = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(row.as_bytes())
+            .records()
+            .next()
+            .unwrap()
+            .unwrap();
+        let row: Vec<String> = record.iter().map(String::from).collect();
+        UnidicBuilder::new(Box::new(JPreprocessSerializer)).convert_row(&row)
+    }
+
+    fn load(row: &[String]) -> WordEntry {
+        let mut details: Vec<&str> = row[4..].iter().map(|s| s.as_str()).collect();
+        details.resize(12, "");
+        WordEntry::load(&details).unwrap()
+    }
+
+    #[test]
+    fn noun() {
+        let row = convert("日本,4786,4786,4160,名詞,固有名詞,地名,国,*,*,ニッポン,日本,日本,ニッポン,日本,ニッポン,固,*,*,*,*,*,*,体,ニッポン,ニッポン,ニッポン,ニッポン,3,C1,*,7637911000000000,27787");
+        assert_eq!(
+            row,
+            "日本,4786,4786,4160,名詞,固有名詞,地域,国,*,*,日本,ニッポン,ニッポン,3/4,C1"
+                .split(',')
+                .collect::<Vec<_>>()
+        );
+
+        let WordEntry::Single(details) = load(&row) else {
+            unreachable!()
+        };
+        assert_eq!(
+            details.pos,
+            POS::Meishi(Meishi::KoyuMeishi(KoyuMeishi::Region(Region::Country)))
+        );
+        assert_eq!(details.acc, 3);
+        assert_eq!(details.mora_size, 4);
+    }
+
+    #[test]
+    fn verb() {
+        let row = convert("書い,15111,15111,6085,動詞,一般,*,*,五段-カ行,連用形-イ音便,カク,書く,書い,カイ,書く,カク,和,*,*,*,*,*,*,用,カイ,カク,カイ,カク,1,C1,*,1601454403174017,5826");
+        assert_eq!(
+            row[8..15],
+            [
+                "五段・カ行イ音便",
+                "連用タ接続",
+                "書く",
+                "カイ",
+                "カイ",
+                "1/2",
+                "C1"
+            ]
+        );
+
+        let WordEntry::Single(details) = load(&row) else {
+            unreachable!()
+        };
+        assert_eq!(details.pos, POS::Doushi(Doushi::Jiritsu));
+        assert_eq!(details.cform, CForm::RenyouConjunctionTa);
+    }
+
+    #[test]
+    fn particle() {
+        let row = convert("が,3950,3950,4118,助詞,格助詞,*,*,*,*,ガ,が,が,ガ,が,ガ,和,*,*,*,*,*,*,助,ガ,ガ,ガ,ガ,*,\"動詞%F2@0,形容詞%F2@-1,名詞%F1\",*,2168520528634368,7889");
+        assert_eq!(row[4..8], ["助詞", "格助詞", "一般", "*"]);
+        assert_eq!(row[14], "動詞%F2@0/形容詞%F2@-1/名詞%F1");
+
+        let WordEntry::Single(details) = load(&row) else {
+            unreachable!()
+        };
+        assert_eq!(
+            details.pos,
+            POS::Joshi(Joshi::KakuJoshi(KakuJoshi::General))
+        );
+        let rule = details
+            .chain_rule
+            .get_rule(&POS::Doushi(Doushi::Jiritsu))
+            .unwrap();
+        assert_eq!(rule.accent_type, AccentType::F2);
+        assert_eq!(rule.add_type, 0);
+    }
+
+    #[test]
+    fn unsupported_chain_rule() {
+        assert_eq!(convert_chain_rule("M4@1"), "*");
+        assert_eq!(convert_chain_rule("形状詞%F2@0,C3"), "C3");
+        assert_eq!(convert_chain_rule("*"), "*");
+    }
+
+    #[test]
+    fn adjective() {
+        let row = convert("赤い,19,19,5000,形容詞,一般,*,*,形容詞,終止形-一般,アカイ,赤い,赤い,アカイ,赤い,アカイ,和,*,*,*,*,*,*,相,アカイ,アカイ,アカイ,アカイ,0,C1,*,1,1");
+        assert_eq!(row[8..10], ["形容詞・アウオ段", "基本形"]);
+        let WordEntry::Single(details) = load(&row) else {
+            unreachable!()
+        };
+        assert_eq!(details.ctype, CType::Keiyoushi(Keiyoushi::Auo));
+
+        // classical adjective
+        let row = convert("無き,19,19,5000,形容詞,一般,*,*,文語形容詞-ク,連体形-一般,ナシ,無し,無き,ナキ,無し,ナシ,和,*,*,*,*,*,*,相,ナキ,ナシ,ナキ,ナシ,1,C1,*,1,1");
+        assert_eq!(row[8..10], ["*", "基本形"]);
+        load(&row);
+    }
+
+    #[test]
+    fn stem() {
+        let row = convert("寒,19,19,5000,形容詞,一般,*,*,形容詞,語幹-一般,サムイ,寒い,寒,サム,寒い,サムイ,和,*,*,*,*,*,*,相,サム,サムイ,サム,サムイ,1,C1,*,1,1");
+        assert_eq!(row[8..10], ["形容詞・アウオ段", "*"]);
+        let WordEntry::Single(details) = load(&row) else {
+            unreachable!()
+        };
+        assert_eq!(details.cform, CForm::None);
+    }
+
+    #[test]
+    fn symbol() {
+        let row = convert(
+            "、,6,6,-3522,補助記号,読点,*,*,*,*,,、,、,,、,,記号,*,*,*,*,*,*,補助,,,,,*,*,*,1,1",
+        );
+        assert_eq!(
+            row[4..15],
+            ["記号", "読点", "*", "*", "*", "*", "、", "*", "、", "0/0", "*"]
+        );
+        load(&row);
+    }
+
+    #[test]
+    fn unk() {
+        assert_eq!(
+            UnidicBuilder::convert_unk("DEFAULT,5968,5968,15124,補助記号,一般,*,*,*,*\n"),
+            "DEFAULT,5968,5968,15124,*,*,*,*,*,*,*"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 pub mod lookup;
 pub mod metadata;
+pub mod overrides;
 pub mod query;
 
 pub use lookup::*;
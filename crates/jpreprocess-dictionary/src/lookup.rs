@@ -37,7 +37,7 @@ pub enum WordDictionaryMode {
 }
 
 impl WordDictionaryMode {
-    pub fn get_word(&self, query: &(dyn DictionaryQuery)) -> JPreprocessResult<WordEntry> {
+    pub fn get_word(&self, query: &dyn DictionaryQuery) -> JPreprocessResult<WordEntry> {
         let details_bin = Self::get_word_binary(query)?;
         match self {
             Self::Lindera => {
@@ -46,15 +46,11 @@ impl WordDictionaryMode {
                 details_str.resize(13, "");
                 WordEntry::load(&details_str)
             }
-            Self::JPreprocess => {
-                let details: WordEntry = bincode::deserialize(details_bin)
-                    .map_err(|err| JPreprocessErrorKind::WordNotFoundError.with_error(err))?;
-                Ok(details)
-            }
+            Self::JPreprocess => WordEntry::from_bytes(details_bin),
         }
     }
 
-    pub fn debug_get_word(&self, query: &(dyn DictionaryQuery)) -> String {
+    pub fn debug_get_word(&self, query: &dyn DictionaryQuery) -> String {
         let details_bin = match Self::get_word_binary(query) {
             Ok(details_bin) => details_bin,
             Err(err) => return format!("Error: {:?}", err),
@@ -64,14 +60,14 @@ impl WordDictionaryMode {
                 Ok(details_str) => details_str.join(","),
                 Err(err) => format!("Error: {:?}", err),
             },
-            Self::JPreprocess => match bincode::deserialize::<'_, WordEntry>(details_bin) {
+            Self::JPreprocess => match WordEntry::from_bytes(details_bin) {
                 Ok(details) => format!("{:?}", details),
                 Err(err) => format!("Error: {:?}", err),
             },
         }
     }
 
-    fn get_word_binary(query: &(dyn DictionaryQuery)) -> JPreprocessResult<&[u8]> {
+    fn get_word_binary(query: &dyn DictionaryQuery) -> JPreprocessResult<&[u8]> {
         let (words_idx_data, words_data) = if query.word_id().is_system() {
             (
                 &query.dictionary().words_idx_data[..],
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use jpreprocess_core::{
+    accent_rule::ChainRules, pos::POS, pronunciation::Pronunciation, word_details::WordDetails,
+    word_entry::WordEntry,
+};
+use lindera_core::word_entry::WordId;
+
+/// Replacement of some fields of a dictionary word. `None` keeps the original value.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WordOverride {
+    /// If `mora_size` is not set, the mora size is updated to that of this pronunciation.
+    pub pron: Option<Pronunciation>,
+    pub acc: Option<i32>,
+    pub mora_size: Option<i32>,
+    pub chain_rule: Option<ChainRules>,
+}
+
+impl WordOverride {
+    fn apply(&self, details: &mut WordDetails) {
+        if let Some(pron) = &self.pron {
+            details.pron = pron.clone();
+            details.mora_size = pron.mora_size() as i32;
+        }
+        if let Some(acc) = self.acc {
+            details.acc = acc;
+        }
+        if let Some(mora_size) = self.mora_size {
+            details.mora_size = mora_size;
+        }
+        if let Some(chain_rule) = &self.chain_rule {
+            details.chain_rule = chain_rule.clone();
+        }
+    }
+}
+
+/// Table of [`WordOverride`] consulted after looking up a word in the dictionary.
+///
+/// Unlike a user dictionary entry, an override does not change the tokenization.
+#[derive(Clone, Debug, Default)]
+pub struct WordOverrides {
+    by_surface: HashMap<String, Vec<(POS, WordOverride)>>,
+    by_word_id: HashMap<(u32, bool), WordOverride>,
+}
+
+impl WordOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.by_surface.is_empty() && self.by_word_id.is_empty()
+    }
+
+    /// Overrides the words with this surface and POS.
+    ///
+    /// For a word consisting of multiple parts (e.g. `あーあ`), each part is matched by
+    /// its own surface.
+    pub fn insert_surface(&mut self, surface: &str, pos: POS, word_override: WordOverride) {
+        let overrides = self.by_surface.entry(surface.to_string()).or_default();
+        match overrides.iter_mut().find(|(p, _)| *p == pos) {
+            Some((_, existing)) => *existing = word_override,
+            None => overrides.push((pos, word_override)),
+        }
+    }
+
+    /// Overrides the word with this id. This takes precedence over [`Self::insert_surface`].
+    ///
+    /// The override targets the whole entry. If the word consists of multiple parts
+    /// (e.g. `あーあ`), the parts are merged into one word before the override is applied:
+    /// the pronunciations are joined, the accent is that of the first part,
+    /// and the POS and conjugation are those of the last part.
+    pub fn insert_word_id(&mut self, word_id: WordId, word_override: WordOverride) {
+        self.by_word_id
+            .insert((word_id.0, word_id.1), word_override);
+    }
+
+    pub fn apply(&self, word_id: WordId, surface: &str, entry: &mut WordEntry) {
+        if let Some(word_override) = self.by_word_id.get(&(word_id.0, word_id.1)) {
+            if let Some(merged) = merge_parts(entry) {
+                *entry = WordEntry::Single(merged);
+            }
+            if let WordEntry::Single(details) = entry {
+                word_override.apply(details);
+            }
+            return;
+        }
+
+        match entry {
+            WordEntry::Single(details) => {
+                if let Some(word_override) = self.find_surface(surface, &details.pos) {
+                    word_override.apply(details);
+                }
+            }
+            WordEntry::Multiple(parts) => {
+                for (orig, details) in parts {
+                    if let Some(word_override) = self.find_surface(orig, &details.pos) {
+                        word_override.apply(details);
+                    }
+                }
+            }
+        }
+    }
+
+    fn find_surface(&self, surface: &str, pos: &POS) -> Option<&WordOverride> {
+        self.by_surface
+            .get(surface)?
+            .iter()
+            .find(|(p, _)| p == pos)
+            .map(|(_, word_override)| word_override)
+    }
+}
+
+/// Merges the parts of a word into one. Returns `None` if the entry is not [`WordEntry::Multiple`].
+fn merge_parts(entry: &WordEntry) -> Option<WordDetails> {
+    let WordEntry::Multiple(parts) = entry else {
+        return None;
+    };
+    let ((_, first), (_, last)) = (parts.first()?, parts.last()?);
+
+    let mut details = last.clone();
+    details.acc = first.acc;
+    details.chain_rule = first.chain_rule.clone();
+    details.chain_flag = first.chain_flag;
+    details.read = parts.iter().map(|(_, part)| part.read.as_deref()).collect();
+    details.pron = Pronunciation::new(
+        parts
+            .iter()
+            .flat_map(|(_, part)| part.pron.moras().iter().cloned())
+            .collect(),
+    );
+    details.mora_size = parts.iter().map(|(_, part)| part.mora_size).sum();
+    Some(details)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use jpreprocess_core::{
+        pos::POS, pronunciation::Pronunciation, word_details::WordDetails, word_entry::WordEntry,
+    };
+    use lindera_core::word_entry::WordId;
+
+    use super::{WordOverride, WordOverrides};
+
+    fn load(row: &str) -> WordEntry {
+        let mut details: Vec<&str> = row.split(',').collect();
+        details.resize(12, "");
+        WordEntry::load(&details).unwrap()
+    }
+
+    fn single(entry: &WordEntry) -> &WordDetails {
+        match entry {
+            WordEntry::Single(details) => details,
+            WordEntry::Multiple(_) => panic!("not a single entry"),
+        }
+    }
+
+    #[test]
+    fn surface_and_pos() {
+        let mut overrides = WordOverrides::default();
+        overrides.insert_surface(
+            "日本",
+            POS::from_strs("名詞", "固有名詞", "地域", "国").unwrap(),
+            WordOverride {
+                pron: Some(Pronunciation::from_str("ニッポン").unwrap()),
+                acc: Some(3),
+                ..Default::default()
+            },
+        );
+
+        let mut entry = load("名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2/3,C1");
+        overrides.apply(WordId(0, true), "日本", &mut entry);
+        let details = single(&entry);
+        assert_eq!(details.pron.to_string(), "ニッポン");
+        assert_eq!((details.acc, details.mora_size), (3, 4));
+
+        // POS does not match
+        let mut entry = load("名詞,一般,*,*,*,*,日本,ニホン,ニホン,2/3,C1");
+        overrides.apply(WordId(1, true), "日本", &mut entry);
+        assert_eq!(single(&entry).pron.to_string(), "ニホン");
+    }
+
+    #[test]
+    fn word_id() {
+        let mut overrides = WordOverrides::default();
+        overrides.insert_surface(
+            "日本",
+            POS::from_strs("名詞", "固有名詞", "地域", "国").unwrap(),
+            WordOverride {
+                acc: Some(3),
+                ..Default::default()
+            },
+        );
+        overrides.insert_word_id(
+            WordId(10, true),
+            WordOverride {
+                acc: Some(0),
+                ..Default::default()
+            },
+        );
+
+        let mut entry = load("名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2/3,C1");
+        overrides.apply(WordId(10, true), "日本", &mut entry);
+        assert_eq!(single(&entry).acc, 0);
+
+        let mut entry = load("名詞,固有名詞,地域,国,*,*,日本,ニホン,ニホン,2/3,C1");
+        overrides.apply(WordId(10, false), "日本", &mut entry);
+        assert_eq!(single(&entry).acc, 3);
+    }
+
+    #[test]
+    fn multiple() {
+        let mut overrides = WordOverrides::default();
+        overrides.insert_surface(
+            "あ",
+            POS::from_strs("感動詞", "*", "*", "*").unwrap(),
+            WordOverride {
+                acc: Some(0),
+                ..Default::default()
+            },
+        );
+
+        let mut entry = load("感動詞,*,*,*,*,*,あー:あ,アー:ア,アー:ア,1/2:1/1,C1");
+        overrides.apply(WordId(0, true), "あーあ", &mut entry);
+        match entry {
+            WordEntry::Multiple(parts) => {
+                assert_eq!(parts[0].1.acc, 1);
+                assert_eq!(parts[1].1.acc, 0);
+            }
+            WordEntry::Single(_) => panic!("not a multiple entry"),
+        }
+    }
+
+    #[test]
+    fn multiple_word_id() {
+        let mut overrides = WordOverrides::default();
+        overrides.insert_word_id(
+            WordId(5, true),
+            WordOverride {
+                acc: Some(0),
+                ..Default::default()
+            },
+        );
+
+        let mut entry = load("感動詞,*,*,*,*,*,あー:あ,アー:ア,アー:ア,1/2:1/1,C1");
+        overrides.apply(WordId(5, true), "あーあ", &mut entry);
+        let details = single(&entry);
+        assert_eq!(details.read.as_deref(), Some("アーア"));
+        assert_eq!(details.pron.to_string(), "アーア");
+        assert_eq!((details.acc, details.mora_size), (0, 3));
+
+        let mut entry = load("感動詞,*,*,*,*,*,あー:あ,アー:ア,アー:ア,1/2:1/1,C1");
+        overrides.apply(WordId(5, false), "あーあ", &mut entry);
+        assert!(matches!(entry, WordEntry::Multiple(_)));
+    }
+}
@@ -0,0 +1,211 @@
+use std::{fmt::Display, str::FromStr, time::Duration};
+
+use jpreprocess_core::{error::JPreprocessErrorKind, JPreprocessError, JPreprocessResult};
+
+use super::feature::utterance_to_phoneme_vec;
+use super::label::Utterance;
+
+/// The time unit of HTS label files, in nanoseconds.
+const HTS_TIME_UNIT_NANOS: u128 = 100;
+
+/// Converts JPCommon Utterance to monophone labels (`sil`, `k`, `o`, ...)
+pub fn utterance_to_monophones(utterance: &Utterance) -> Vec<String> {
+    utterance_to_phoneme_vec(utterance)
+        .into_iter()
+        .map(|(phoneme, _)| phoneme)
+        .collect()
+}
+
+/// A line of HTS label file (`.lab`), which is `start end label` or `label`.
+///
+/// The time is in the HTS time unit (100 ns).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LabLine {
+    pub time: Option<(u64, u64)>,
+    pub label: String,
+}
+
+impl LabLine {
+    /// The duration of the label, if the line has time and it does not end before it starts.
+    pub fn duration(&self) -> Option<Duration> {
+        let (start, end) = self.time?;
+        let duration = end.checked_sub(start)?;
+        Some(Duration::from_nanos(
+            duration.checked_mul(HTS_TIME_UNIT_NANOS as u64)?,
+        ))
+    }
+}
+
+impl Display for LabLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.time {
+            Some((start, end)) => write!(f, "{} {} {}", start, end, self.label),
+            None => write!(f, "{}", self.label),
+        }
+    }
+}
+
+impl FromStr for LabLine {
+    type Err = JPreprocessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        match fields[..] {
+            [label] => Ok(Self {
+                time: None,
+                label: label.to_string(),
+            }),
+            [start, end, label] => {
+                let parse = |time: &str| {
+                    time.parse::<u64>().map_err(|err| {
+                        JPreprocessErrorKind::LabelFormatError.with_error(anyhow::anyhow!(
+                            "Invalid time {} in {}: {}",
+                            time,
+                            s,
+                            err
+                        ))
+                    })
+                };
+                let (start, end) = (parse(start)?, parse(end)?);
+                if start > end {
+                    return Err(JPreprocessErrorKind::LabelFormatError
+                        .with_error(anyhow::anyhow!("The label ends before it starts: {}", s)));
+                }
+                Ok(Self {
+                    time: Some((start, end)),
+                    label: label.to_string(),
+                })
+            }
+            _ => Err(JPreprocessErrorKind::LabelFormatError
+                .with_error(anyhow::anyhow!("Invalid line of label file: {}", s))),
+        }
+    }
+}
+
+/// Makes the lines of HTS label file from the labels (fullcontext or monophone).
+///
+/// If `durations` is given, the time columns are filled so that each label starts
+/// where the previous one ends. The number of durations must match the number of labels.
+pub fn labels_to_lab(
+    labels: Vec<String>,
+    durations: Option<&[Duration]>,
+) -> JPreprocessResult<Vec<LabLine>> {
+    let Some(durations) = durations else {
+        return Ok(labels
+            .into_iter()
+            .map(|label| LabLine { time: None, label })
+            .collect());
+    };
+    if durations.len() != labels.len() {
+        return Err(
+            JPreprocessErrorKind::LabelFormatError.with_error(anyhow::anyhow!(
+                "{} durations are given for {} labels",
+                durations.len(),
+                labels.len()
+            )),
+        );
+    }
+
+    // The boundaries are converted from the total duration, so that the rounding errors
+    // of the durations do not accumulate.
+    let mut total = Duration::ZERO;
+    let mut start = 0;
+    Ok(labels
+        .into_iter()
+        .zip(durations)
+        .map(|(label, duration)| {
+            total += *duration;
+            let end = (total.as_nanos() / HTS_TIME_UNIT_NANOS) as u64;
+            let line = LabLine {
+                time: Some((start, end)),
+                label,
+            };
+            start = end;
+            line
+        })
+        .collect())
+}
+
+/// Parses HTS label file. Empty lines are skipped.
+pub fn parse_lab(lab: &str) -> JPreprocessResult<Vec<LabLine>> {
+    lab.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(LabLine::from_str)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use jpreprocess_njd::NJDNode;
+
+    use crate::Utterance;
+
+    use super::*;
+
+    #[test]
+    fn monophones_bonsai() {
+        let njd = vec![NJDNode::new_single(
+            "盆栽,名詞,一般,*,*,*,*,盆栽,ボンサイ,ボンサイ,0/4,C2",
+        )];
+        let utterance = Utterance::from(njd.as_slice());
+        assert_eq!(
+            utterance_to_monophones(&utterance),
+            ["sil", "b", "o", "N", "s", "a", "i", "sil"]
+        );
+    }
+
+    #[test]
+    fn lab_round_trip() {
+        let labels = vec!["sil".to_string(), "a".to_string(), "sil".to_string()];
+        let durations = [
+            Duration::from_millis(100),
+            Duration::from_millis(125),
+            Duration::from_millis(50),
+        ];
+
+        let lab = labels_to_lab(labels.clone(), Some(&durations)).unwrap();
+        let text: String = lab.iter().map(|line| format!("{}\n", line)).collect();
+        assert_eq!(
+            text,
+            "0 1000000 sil\n1000000 2250000 a\n2250000 2750000 sil\n"
+        );
+
+        let parsed = parse_lab(&text).unwrap();
+        assert_eq!(parsed, lab);
+        let parsed_durations: Vec<Duration> = parsed.iter().filter_map(LabLine::duration).collect();
+        assert_eq!(parsed_durations, durations);
+
+        let lab = labels_to_lab(labels, None).unwrap();
+        assert_eq!(lab[1].to_string(), "a");
+        assert_eq!(parse_lab("sil\na\n\nsil\n").unwrap(), lab);
+    }
+
+    #[test]
+    fn lab_without_drift() {
+        let labels = vec!["a".to_string(); 3];
+        let durations = [Duration::from_nanos(150); 3];
+
+        let lab = labels_to_lab(labels, Some(&durations)).unwrap();
+        assert_eq!(
+            lab.iter().map(|line| line.time).collect::<Vec<_>>(),
+            [Some((0, 1)), Some((1, 3)), Some((3, 4))]
+        );
+    }
+
+    #[test]
+    fn lab_errors() {
+        let labels = vec!["sil".to_string(), "sil".to_string()];
+        assert!(labels_to_lab(labels, Some(&[Duration::from_millis(100)])).is_err());
+        for lab in ["0 100", "a 100 sil", "100 0 sil", "0 100 sil extra"] {
+            assert!(parse_lab(lab).is_err(), "{}", lab);
+        }
+
+        let line = LabLine {
+            time: Some((100, 0)),
+            label: "sil".to_string(),
+        };
+        assert_eq!(line.duration(), None);
+    }
+}
@@ -1,8 +1,10 @@
 mod feature;
+mod lab;
 mod label;
 mod word_attr;
 
 pub use feature::*;
+pub use lab::*;
 pub use label::*;
 
 use jpreprocess_njd::NJDNode;
@@ -12,3 +14,9 @@ pub fn njdnodes_to_features(njd_nodes: &[NJDNode]) -> Vec<String> {
     let utterance = Utterance::from(njd_nodes);
     utterance_to_features(&utterance)
 }
+
+/// Converts NJDNode to monophone labels
+pub fn njdnodes_to_monophones(njd_nodes: &[NJDNode]) -> Vec<String> {
+    let utterance = Utterance::from(njd_nodes);
+    utterance_to_monophones(&utterance)
+}
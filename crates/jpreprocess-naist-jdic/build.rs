@@ -1,5 +1,29 @@
 use std::error::Error;
 
+/// Path to a tar.gz archive or a directory of naist-jdic source to be used instead of
+/// downloading the released one.
+#[cfg(feature = "naist-jdic")]
+const SOURCE_ENV: &str = "JPREPROCESS_NAIST_JDIC_SOURCE";
+/// Expected SHA-256 (hex) of the source archive, overriding [`DEFAULT_SHA256`].
+#[cfg(feature = "naist-jdic")]
+const SHA256_ENV: &str = "JPREPROCESS_NAIST_JDIC_SHA256";
+/// SHA-256 (hex) of the released archive downloaded by default (v0.1.1), if pinned.
+///
+/// It is not pinned yet, so the downloaded archive is verified only if [`SHA256_ENV`] is set.
+#[cfg(feature = "naist-jdic")]
+const DEFAULT_SHA256: Option<&str> = None;
+/// Files embedded by `src/lindera.rs`, which are compressed with `compress` feature.
+#[cfg(feature = "compress")]
+const EMBEDDED_FILES: [&str; 7] = [
+    "char_def.bin",
+    "matrix.mtx",
+    "dict.da",
+    "dict.vals",
+    "unk.bin",
+    "dict.wordsidx",
+    "dict.words",
+];
+
 #[cfg(feature = "naist-jdic")]
 fn main() -> Result<(), Box<dyn Error>> {
     use jpreprocess_dictionary_builder::{
@@ -8,9 +32,9 @@ fn main() -> Result<(), Box<dyn Error>> {
     use lindera_core::dictionary_builder::DictionaryBuilder;
     use std::{
         env,
-        fs::{copy, create_dir, rename, File},
+        fs::{copy, create_dir, remove_dir_all, rename, File},
         io::{self, Cursor, Read, Write},
-        path::Path,
+        path::{Path, PathBuf},
     };
 
     use encoding::{
@@ -22,6 +46,8 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-changed=Cargo.toml");
+    println!("cargo:rerun-if-env-changed={}", SOURCE_ENV);
+    println!("cargo:rerun-if-env-changed={}", SHA256_ENV);
 
     // Directory path for build package
     let build_dir = env::var_os("OUT_DIR").unwrap(); // ex) target/debug/build/<pkg>/out
@@ -29,10 +55,28 @@ fn main() -> Result<(), Box<dyn Error>> {
     // Dictionary file name
     let file_name = "v0.1.1.tar.gz";
 
-    // MeCab IPADIC directory
-    let input_dir = Path::new(&build_dir).join("naist-jdic-0.1.1");
+    // Directory to decompress the source archive into
+    let source_dir = Path::new(&build_dir).join("naist-jdic-src");
+
+    // Expected SHA-256 of the source archive given by the user
+    let expected_sha256 = env::var(SHA256_ENV).ok();
+
+    // Decompresses a tar.gz archive into `dest`, and returns the directory containing matrix.def
+    let unpack = |buffer: Vec<u8>, dest: &Path| -> Result<PathBuf, Box<dyn Error>> {
+        if dest.exists() {
+            remove_dir_all(dest)?;
+        }
+        let gzdecoder = GzDecoder::new(Cursor::new(buffer));
+        let mut archive = Archive::new(gzdecoder);
+        archive.unpack(dest)?;
+
+        find_source_dir(dest)
+    };
+
+    let input_dir = if std::env::var("DOCS_RS").is_ok() {
+        // MeCab IPADIC directory
+        let input_dir = Path::new(&build_dir).join("naist-jdic-0.1.1");
 
-    if std::env::var("DOCS_RS").is_ok() {
         // Create directory for dummy input directory for build docs
         create_dir(&input_dir)?;
 
@@ -55,6 +99,29 @@ fn main() -> Result<(), Box<dyn Error>> {
         File::create(input_dir.join("unk.def"))?;
         let mut dummy_matrix_def = File::create(input_dir.join("matrix.def"))?;
         dummy_matrix_def.write_all(b"0 1 0\n")?;
+
+        input_dir
+    } else if let Some(source) = env::var_os(SOURCE_ENV) {
+        // Local source (e.g. a patched naist-jdic) instead of the released one
+        let source = PathBuf::from(source);
+        println!("cargo:rerun-if-changed={}", source.display());
+
+        if source.is_dir() {
+            if expected_sha256.is_some() {
+                println!(
+                    "cargo:warning={} has no effect, as {} is a directory",
+                    SHA256_ENV, SOURCE_ENV
+                );
+            }
+            find_source_dir(&source)?
+        } else {
+            let mut buffer = Vec::new();
+            File::open(&source)?.read_to_end(&mut buffer)?;
+            if let Some(expected) = &expected_sha256 {
+                verify_sha256(&buffer, expected)?;
+            }
+            unpack(buffer, &source_dir)?
+        }
     } else {
         // Source file path for build package
         let source_path_for_build = Path::new(&build_dir).join(file_name);
@@ -80,11 +147,17 @@ fn main() -> Result<(), Box<dyn Error>> {
         let mut tar_gz = File::open(source_path_for_build)?;
         let mut buffer = Vec::new();
         tar_gz.read_to_end(&mut buffer)?;
-        let cursor = Cursor::new(buffer);
-        let gzdecoder = GzDecoder::new(cursor);
-        let mut archive = Archive::new(gzdecoder);
-        archive.unpack(&build_dir)?;
-    }
+
+        // The released archive is verified against the pinned checksum, unless overridden
+        match expected_sha256.as_deref().or(DEFAULT_SHA256) {
+            Some(expected) => verify_sha256(&buffer, expected)?,
+            None => println!(
+                "cargo:warning=The checksum of {} is not pinned; set {} to verify it",
+                file_name, SHA256_ENV
+            ),
+        }
+        unpack(buffer, &source_dir)?
+    };
 
     // Lindera IPADIC directory
     let output_dir = Path::new(&build_dir).join("naist-jdic");
@@ -93,6 +166,18 @@ fn main() -> Result<(), Box<dyn Error>> {
     let builder = IpadicBuilder::new(Box::new(JPreprocessSerializer));
     builder.build_dictionary(&input_dir, &output_dir)?;
 
+    #[cfg(feature = "compress")]
+    {
+        use jpreprocess_dictionary_builder::compress::compress;
+        use lindera_decompress::Algorithm;
+
+        for file in EMBEDDED_FILES {
+            let path = output_dir.join(file);
+            let data = std::fs::read(&path)?;
+            std::fs::write(&path, compress(&data, Algorithm::Deflate)?)?;
+        }
+    }
+
     let license_file = &input_dir.join(Path::new("COPYING"));
     if license_file.exists() {
         copy(license_file, output_dir.join(Path::new("COPYING")))?;
@@ -101,6 +186,41 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Fails unless the SHA-256 of `buffer` is `expected` (hex).
+#[cfg(feature = "naist-jdic")]
+fn verify_sha256(buffer: &[u8], expected: &str) -> Result<(), Box<dyn Error>> {
+    use sha2::{Digest, Sha256};
+
+    let actual: String = Sha256::digest(buffer)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        return Err(format!(
+            "Checksum mismatch of naist-jdic source archive: expected {}, found {}",
+            expected, actual
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Returns `dir` or its only subdirectory, whichever contains matrix.def.
+#[cfg(feature = "naist-jdic")]
+fn find_source_dir(dir: &std::path::Path) -> Result<std::path::PathBuf, Box<dyn Error>> {
+    if dir.join("matrix.def").exists() {
+        return Ok(dir.to_path_buf());
+    }
+    let subdirs: Vec<_> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    match subdirs.as_slice() {
+        [subdir] if subdir.join("matrix.def").exists() => Ok(subdir.to_path_buf()),
+        _ => Err(format!("matrix.def is not found in {}", dir.display()).into()),
+    }
+}
+
 #[cfg(not(feature = "naist-jdic"))]
 fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
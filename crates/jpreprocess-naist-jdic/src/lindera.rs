@@ -6,73 +6,179 @@ use lindera_core::{
     LinderaResult,
 };
 
+/// Dictionary data embedded in the binary.
+///
+/// With `compress` feature, the data is compressed at build time and decompressed on first access.
+struct Embedded {
+    data: &'static [u8],
+    #[cfg(feature = "compress")]
+    decompressed: once_cell::sync::OnceCell<Vec<u8>>,
+}
+
+impl Embedded {
+    const fn new(data: &'static [u8]) -> Self {
+        Self {
+            data,
+            #[cfg(feature = "compress")]
+            decompressed: once_cell::sync::OnceCell::new(),
+        }
+    }
+
+    #[cfg(not(feature = "compress"))]
+    fn get(&'static self) -> LinderaResult<&'static [u8]> {
+        Ok(self.data)
+    }
+
+    #[cfg(feature = "compress")]
+    fn get(&'static self) -> LinderaResult<&'static [u8]> {
+        use lindera_core::error::LinderaErrorKind;
+        use lindera_decompress::CompressedData;
+
+        self.decompressed
+            .get_or_try_init(|| {
+                let compressed: CompressedData =
+                    bincode::deserialize(self.data).map_err(|err| {
+                        LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!(err))
+                    })?;
+                lindera_decompress::decompress(compressed)
+                    .map_err(|err| LinderaErrorKind::Decode.with_error(err))
+            })
+            .map(|data| data.as_slice())
+    }
+
+    /// Same as [`Embedded::get`], which never fails without `compress` feature.
+    fn expect(&'static self) -> &'static [u8] {
+        self.get()
+            .expect("Failed to decompress the bundled dictionary")
+    }
+}
+
 #[cfg(feature = "naist-jdic")]
-const CHAR_DEFINITION_DATA: &[u8] =
-    include_bytes!(concat!(env!("OUT_DIR"), "/naist-jdic/char_def.bin"));
+static CHAR_DEFINITION_DATA: Embedded = Embedded::new(include_bytes!(concat!(
+    env!("OUT_DIR"),
+    "/naist-jdic/char_def.bin"
+)));
 #[cfg(not(feature = "naist-jdic"))]
-const CHAR_DEFINITION_DATA: &[u8] = &[];
+static CHAR_DEFINITION_DATA: Embedded = Embedded::new(&[]);
 
 #[cfg(feature = "naist-jdic")]
-const CONNECTION_DATA: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/naist-jdic/matrix.mtx"));
+static CONNECTION_DATA: Embedded = Embedded::new(include_bytes!(concat!(
+    env!("OUT_DIR"),
+    "/naist-jdic/matrix.mtx"
+)));
 #[cfg(not(feature = "naist-jdic"))]
-const CONNECTION_DATA: &[u8] = &[];
+static CONNECTION_DATA: Embedded = Embedded::new(&[]);
 
 #[cfg(feature = "naist-jdic")]
-const IPADIC_DATA: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/naist-jdic/dict.da"));
+static IPADIC_DATA: Embedded = Embedded::new(include_bytes!(concat!(
+    env!("OUT_DIR"),
+    "/naist-jdic/dict.da"
+)));
 #[cfg(not(feature = "naist-jdic"))]
-const IPADIC_DATA: &[u8] = &[];
+static IPADIC_DATA: Embedded = Embedded::new(&[]);
 
 #[cfg(feature = "naist-jdic")]
-const IPADIC_VALS: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/naist-jdic/dict.vals"));
+static IPADIC_VALS: Embedded = Embedded::new(include_bytes!(concat!(
+    env!("OUT_DIR"),
+    "/naist-jdic/dict.vals"
+)));
 #[cfg(not(feature = "naist-jdic"))]
-const IPADIC_VALS: &[u8] = &[];
+static IPADIC_VALS: Embedded = Embedded::new(&[]);
 
 #[cfg(feature = "naist-jdic")]
-const UNKNOWN_DATA: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/naist-jdic/unk.bin"));
+static UNKNOWN_DATA: Embedded = Embedded::new(include_bytes!(concat!(
+    env!("OUT_DIR"),
+    "/naist-jdic/unk.bin"
+)));
 #[cfg(not(feature = "naist-jdic"))]
-const UNKNOWN_DATA: &[u8] = &[];
+static UNKNOWN_DATA: Embedded = Embedded::new(&[]);
 
 #[cfg(feature = "naist-jdic")]
-const WORDS_IDX_DATA: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/naist-jdic/dict.wordsidx"));
+static WORDS_IDX_DATA: Embedded = Embedded::new(include_bytes!(concat!(
+    env!("OUT_DIR"),
+    "/naist-jdic/dict.wordsidx"
+)));
 #[cfg(not(feature = "naist-jdic"))]
-const WORDS_IDX_DATA: &[u8] = &[];
+static WORDS_IDX_DATA: Embedded = Embedded::new(&[]);
 
 #[cfg(feature = "naist-jdic")]
-const WORDS_DATA: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/naist-jdic/dict.words"));
+static WORDS_DATA: Embedded = Embedded::new(include_bytes!(concat!(
+    env!("OUT_DIR"),
+    "/naist-jdic/dict.words"
+)));
 #[cfg(not(feature = "naist-jdic"))]
-const WORDS_DATA: &[u8] = &[];
+static WORDS_DATA: Embedded = Embedded::new(&[]);
 
 pub fn load_dictionary() -> LinderaResult<Dictionary> {
     Ok(Dictionary {
-        dict: prefix_dict(),
-        cost_matrix: connection(),
+        dict: PrefixDict::from_static_slice(IPADIC_DATA.get()?, IPADIC_VALS.get()?),
+        cost_matrix: ConnectionCostMatrix::load_static(CONNECTION_DATA.get()?),
         char_definitions: char_def()?,
         unknown_dictionary: unknown_dict()?,
-        words_idx_data: words_idx_data(),
-        words_data: words_data(),
+        words_idx_data: Cow::Borrowed(WORDS_IDX_DATA.get()?),
+        words_data: Cow::Borrowed(WORDS_DATA.get()?),
     })
 }
 
 pub fn char_def() -> LinderaResult<CharacterDefinitions> {
-    CharacterDefinitions::load(CHAR_DEFINITION_DATA)
+    CharacterDefinitions::load(CHAR_DEFINITION_DATA.get()?)
 }
 
+/// # Panics
+///
+/// With `compress` feature, panics if the data cannot be decompressed.
+/// `try_connection` returns the error instead.
 pub fn connection() -> ConnectionCostMatrix {
-    ConnectionCostMatrix::load(CONNECTION_DATA)
+    ConnectionCostMatrix::load_static(CONNECTION_DATA.expect())
 }
 
+/// # Panics
+///
+/// With `compress` feature, panics if the data cannot be decompressed.
+/// `try_prefix_dict` returns the error instead.
 pub fn prefix_dict() -> PrefixDict {
-    PrefixDict::from_static_slice(IPADIC_DATA, IPADIC_VALS)
+    PrefixDict::from_static_slice(IPADIC_DATA.expect(), IPADIC_VALS.expect())
 }
 
 pub fn unknown_dict() -> LinderaResult<UnknownDictionary> {
-    UnknownDictionary::load(UNKNOWN_DATA)
+    UnknownDictionary::load(UNKNOWN_DATA.get()?)
 }
 
+/// # Panics
+///
+/// With `compress` feature, panics if the data cannot be decompressed.
+/// `try_words_idx_data` returns the error instead.
 pub fn words_idx_data() -> Cow<'static, [u8]> {
-    Cow::Borrowed(WORDS_IDX_DATA)
+    Cow::Borrowed(WORDS_IDX_DATA.expect())
 }
 
+/// # Panics
+///
+/// With `compress` feature, panics if the data cannot be decompressed.
+/// `try_words_data` returns the error instead.
 pub fn words_data() -> Cow<'static, [u8]> {
-    Cow::Borrowed(WORDS_DATA)
+    Cow::Borrowed(WORDS_DATA.expect())
+}
+
+#[cfg(feature = "compress")]
+pub fn try_connection() -> LinderaResult<ConnectionCostMatrix> {
+    Ok(ConnectionCostMatrix::load_static(CONNECTION_DATA.get()?))
+}
+
+#[cfg(feature = "compress")]
+pub fn try_prefix_dict() -> LinderaResult<PrefixDict> {
+    Ok(PrefixDict::from_static_slice(
+        IPADIC_DATA.get()?,
+        IPADIC_VALS.get()?,
+    ))
+}
+
+#[cfg(feature = "compress")]
+pub fn try_words_idx_data() -> LinderaResult<Cow<'static, [u8]>> {
+    Ok(Cow::Borrowed(WORDS_IDX_DATA.get()?))
+}
+
+#[cfg(feature = "compress")]
+pub fn try_words_data() -> LinderaResult<Cow<'static, [u8]>> {
+    Ok(Cow::Borrowed(WORDS_DATA.get()?))
 }
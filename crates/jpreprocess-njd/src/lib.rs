@@ -1,7 +1,8 @@
+mod mecab;
 mod njd_set;
 mod node;
 
-use jpreprocess_core::JPreprocessResult;
+use jpreprocess_core::{word_entry::WordEntry, JPreprocessResult};
 use jpreprocess_dictionary::WordDictionaryConfig;
 use jpreprocess_window::{IterQuintMut, IterQuintMutTrait};
 use lindera_tokenizer::token::Token;
@@ -33,6 +34,20 @@ impl NJD {
         Ok(Self { nodes })
     }
 
+    /// Builds NJD from words, each of which is a pair of the surface and the dictionary entry.
+    pub fn from_entries<I, S>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (S, WordEntry)>,
+        S: AsRef<str>,
+    {
+        Self {
+            nodes: entries
+                .into_iter()
+                .flat_map(|(text, details)| NJDNode::load(text.as_ref(), details))
+                .collect(),
+        }
+    }
+
     pub fn from_strings(njd_features: Vec<String>) -> Self {
         Self {
             nodes: njd_features
@@ -45,6 +60,11 @@ impl NJD {
     pub fn preprocess(&mut self) {
         njd_set::preprocess_njd(self)
     }
+
+    /// Same as [`Self::preprocess`], but reads the symbols without pronunciation with the table.
+    pub fn preprocess_with_symbols(&mut self, symbols: &pronunciation::SymbolTable) {
+        njd_set::preprocess_njd_with_symbols(self, symbols)
+    }
 }
 
 impl IterQuintMutTrait for NJD {
@@ -0,0 +1,70 @@
+use jpreprocess_core::{error::JPreprocessErrorKind, word_entry::WordEntry, JPreprocessResult};
+
+use crate::NJD;
+
+impl NJD {
+    /// Builds NJD from the output of MeCab with an OpenJTalk-compatible dictionary.
+    ///
+    /// Each line is `surface\tfeatures`, where the features are the same as the columns of
+    /// the dictionary csv after the cost (POS, ctype, cform, orig, read, pron,
+    /// accent/mora size, chain rule and optionally chain flag).
+    /// Empty lines and `EOS` are ignored.
+    pub fn from_mecab(output: &str) -> JPreprocessResult<Self> {
+        let mut entries = vec![];
+        for (i, line) in output.lines().enumerate() {
+            if line.is_empty() || line == "EOS" {
+                continue;
+            }
+            let entry = parse_mecab_line(line).map_err(|err| {
+                err.kind
+                    .with_error(anyhow::anyhow!("line {}: {}: {}", i + 1, line, err))
+            })?;
+            entries.push(entry);
+        }
+        Ok(Self::from_entries(entries))
+    }
+}
+
+fn parse_mecab_line(line: &str) -> JPreprocessResult<(&str, WordEntry)> {
+    let (surface, features) = line.split_once('\t').ok_or_else(|| {
+        JPreprocessErrorKind::WordEntryFormatError.with_error(anyhow::anyhow!(
+            "surface and features must be separated by tab"
+        ))
+    })?;
+    let mut details: Vec<&str> = features.split(',').collect();
+    details.resize(12, "");
+    Ok((surface, WordEntry::load(&details)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::NJD;
+
+    #[test]
+    fn mecab() {
+        let njd = NJD::from_mecab(concat!(
+            "音声\t名詞,一般,*,*,*,*,音声,オンセイ,オンセー,1/4,C1\n",
+            "あーあ\t感動詞,*,*,*,*,*,あー:あ,アー:ア,アー:ア,1/2:1/1,C1\n",
+            "EOS\n",
+        ))
+        .unwrap();
+        let njd: Vec<String> = njd.into();
+        assert_eq!(
+            njd,
+            [
+                "音声,名詞,一般,*,*,*,*,音声,オンセイ,オンセー,1/4,C1,-1",
+                "あー,感動詞,*,*,*,*,*,あー,アー,アー,1/2,C1,-1",
+                "あ,感動詞,*,*,*,*,*,あ,ア,ア,1/1,C1,0",
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(NJD::from_mecab("音声 名詞,一般,*,*,*,*,音声,オンセイ,オンセー,1/4,C1").is_err());
+        let err = NJD::from_mecab("EOS\n音声\t名刺,一般,*,*,*,*,音声,オンセイ,オンセー,1/4,C1")
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("line 2"));
+    }
+}
@@ -43,6 +43,12 @@ pub fn from_njd(njd: &NJD) -> Vec<DigitSequence> {
     }
 
     for seq in &mut result {
+        if let Some(numerical_reading) = njd.nodes[seq.start..=seq.end]
+            .iter()
+            .find_map(|node| node.get_numerical_reading())
+        {
+            seq.is_numerical_reading = Some(numerical_reading);
+        }
         seq.estimate_numerical_reading(njd);
     }
     result
@@ -339,3 +339,40 @@ fn normalize_digit(node: &mut NJDNode) -> bool {
 fn is_period(s: &str) -> bool {
     matches!(s, rule::TEN1 | rule::TEN2)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{NJDNode, NJD};
+
+    use super::njd_set_digit;
+
+    fn digits(numerical_reading: Option<bool>) -> Vec<String> {
+        let mut njd = NJD {
+            nodes: [
+                "一,名詞,数,*,*,*,*,一,イチ,イチ,2/2,*",
+                "二,名詞,数,*,*,*,*,二,ニ,ニ,1/1,*",
+                "三,名詞,数,*,*,*,*,三,サン,サン,0/2,*",
+            ]
+            .iter()
+            .map(|node| {
+                let mut node = NJDNode::new_single(node);
+                if let Some(numerical_reading) = numerical_reading {
+                    node.set_numerical_reading(numerical_reading);
+                }
+                node
+            })
+            .collect(),
+        };
+        njd_set_digit(&mut njd);
+        njd.nodes
+            .iter()
+            .map(|node| node.get_pron().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn numerical_reading_hint() {
+        assert_eq!(digits(Some(false)), ["イチ", "ニー", "サン"]);
+        assert_eq!(digits(Some(true)), ["ヒャク", "ニ", "ジュー", "サン"]);
+    }
+}
@@ -8,7 +8,11 @@ pub mod pronunciation;
 pub mod unvoiced_vowel;
 
 pub fn preprocess_njd(njd: &mut NJD) {
-    pronunciation::njd_set_pronunciation(njd);
+    preprocess_njd_with_symbols(njd, &pronunciation::DEFAULT_SYMBOL_TABLE)
+}
+
+pub fn preprocess_njd_with_symbols(njd: &mut NJD, symbols: &pronunciation::SymbolTable) {
+    pronunciation::njd_set_pronunciation_with_symbols(njd, symbols);
     digit::njd_set_digit(njd);
     accent_phrase::njd_set_accent_phrase(njd);
     accent_type::njd_set_accent_type(njd);
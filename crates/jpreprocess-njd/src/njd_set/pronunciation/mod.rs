@@ -3,6 +3,10 @@ use std::str::FromStr;
 use crate::NJD;
 
 mod rule;
+mod symbol;
+
+pub(crate) use symbol::DEFAULT_SYMBOL_TABLE;
+pub use symbol::{SymbolReading, SymbolTable};
 
 use jpreprocess_core::{
     pos::*,
@@ -12,6 +16,11 @@ use jpreprocess_core::{
 use jpreprocess_window::*;
 
 pub fn njd_set_pronunciation(njd: &mut NJD) {
+    njd_set_pronunciation_with_symbols(njd, &DEFAULT_SYMBOL_TABLE)
+}
+
+/// Same as [`njd_set_pronunciation`], but reads the symbols with the table.
+pub fn njd_set_pronunciation_with_symbols(njd: &mut NJD, symbols: &SymbolTable) {
     for node in &mut njd.nodes {
         if node.get_mora_size() == 0 {
             let pron =
@@ -40,6 +49,8 @@ pub fn njd_set_pronunciation(njd: &mut NJD) {
         }
     }
 
+    symbol::njd_set_symbol_pronunciation(njd, symbols);
+
     njd.remove_silent_node();
 
     /* chain kana sequence */
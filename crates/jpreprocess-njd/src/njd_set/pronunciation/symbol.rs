@@ -0,0 +1,273 @@
+use std::{collections::HashMap, str::FromStr};
+
+use jpreprocess_core::{pos::POS, pronunciation::Pronunciation, JPreprocessResult};
+use once_cell::sync::Lazy;
+
+use crate::{NJDNode, NJD};
+
+pub(crate) static DEFAULT_SYMBOL_TABLE: Lazy<SymbolTable> = Lazy::new(SymbolTable::default);
+
+/// Reading of a symbol, which may depend on the adjacent numbers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymbolReading {
+    default: (Pronunciation, i32),
+    between_numbers: Option<(Pronunciation, i32)>,
+    before_number: Option<(Pronunciation, i32)>,
+}
+
+impl SymbolReading {
+    /// `pron` is in katakana, and `acc` is the accent position.
+    ///
+    /// Empty `pron` drops the symbol, and `、` makes it a pause.
+    pub fn new(pron: &str, acc: i32) -> JPreprocessResult<Self> {
+        Ok(Self {
+            default: parse(pron, acc)?,
+            between_numbers: None,
+            before_number: None,
+        })
+    }
+
+    /// Sets the reading used between two numbers, e.g. `−` in `３−５`.
+    ///
+    /// This is not used in a chain like `０３−１２３４−５６７８`,
+    /// even if the numbers are split into digits.
+    pub fn between_numbers(mut self, pron: &str, acc: i32) -> JPreprocessResult<Self> {
+        self.between_numbers = Some(parse(pron, acc)?);
+        Ok(self)
+    }
+
+    /// Sets the reading used before a number which does not follow another number,
+    /// e.g. `−` in `−５`.
+    pub fn before_number(mut self, pron: &str, acc: i32) -> JPreprocessResult<Self> {
+        self.before_number = Some(parse(pron, acc)?);
+        Ok(self)
+    }
+
+    fn select(&self, nodes: &[NJDNode], index: usize) -> &(Pronunciation, i32) {
+        fn is_number(node: &NJDNode) -> bool {
+            node.get_pos().is_kazu()
+                || (!node.get_string().is_empty()
+                    && node.get_string().chars().all(|c| c.is_numeric()))
+        }
+        // Whether the number next to the symbol, which may be split into digits
+        // (e.g. `１２３４`), is followed by the same symbol as in `０３−１２３４−５６７８`.
+        fn is_chained<'a>(mut neighbors: impl Iterator<Item = &'a NJDNode>, symbol: &str) -> bool {
+            neighbors
+                .find(|node| !is_number(node))
+                .map(|node| node.get_string() == symbol)
+                .unwrap_or(false)
+        }
+
+        let symbol = nodes[index].get_string();
+        let prev = index.checked_sub(1).and_then(|i| nodes.get(i));
+        let next = nodes.get(index + 1);
+        match (prev.map(is_number), next.map(is_number)) {
+            (Some(true), Some(true))
+                if !is_chained(nodes[..index].iter().rev(), symbol)
+                    && !is_chained(nodes[index + 1..].iter(), symbol) =>
+            {
+                self.between_numbers.as_ref()
+            }
+            (None | Some(false), Some(true)) => self.before_number.as_ref(),
+            _ => None,
+        }
+        .unwrap_or(&self.default)
+    }
+}
+
+fn parse(pron: &str, acc: i32) -> JPreprocessResult<(Pronunciation, i32)> {
+    if pron.is_empty() {
+        Ok((Pronunciation::default(), 0))
+    } else {
+        Ok((Pronunciation::from_str(pron)?, acc))
+    }
+}
+
+/// Readings of the symbols which have no pronunciation in the dictionary.
+///
+/// The default table contains the frequently used symbols, e.g. `※`, `→`, `＋` and `−`.
+#[derive(Clone, Debug)]
+pub struct SymbolTable {
+    readings: HashMap<String, SymbolReading>,
+}
+
+impl SymbolTable {
+    pub fn empty() -> Self {
+        Self {
+            readings: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, symbol: &str, reading: SymbolReading) {
+        self.readings.insert(symbol.to_string(), reading);
+    }
+
+    pub fn remove(&mut self, symbol: &str) -> Option<SymbolReading> {
+        self.readings.remove(symbol)
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<&SymbolReading> {
+        self.readings.get(symbol)
+    }
+
+    fn try_default() -> JPreprocessResult<Self> {
+        let mut table = Self::empty();
+        let mut insert = |symbols: &[&str], reading: SymbolReading| {
+            for symbol in symbols {
+                table.insert(symbol, reading.clone());
+            }
+        };
+
+        insert(&["※"], SymbolReading::new("コメジルシ", 3)?);
+        insert(
+            &["→", "←", "↑", "↓", "⇒"],
+            SymbolReading::new("ヤジルシ", 2)?,
+        );
+        insert(&["＆", "&"], SymbolReading::new("アンド", 1)?);
+        insert(&["＝", "="], SymbolReading::new("イコール", 2)?);
+        insert(
+            &["＋", "+"],
+            SymbolReading::new("プラス", 0)?.between_numbers("タス", 0)?,
+        );
+        insert(
+            &["−", "－", "-", "‐"],
+            SymbolReading::new("ハイフン", 0)?
+                .between_numbers("カラ", 0)?
+                .before_number("マイナス", 0)?,
+        );
+        insert(
+            &["〜", "～", "~"],
+            SymbolReading::new("", 0)?.between_numbers("カラ", 0)?,
+        );
+        insert(&["×"], SymbolReading::new("カケル", 2)?);
+        insert(&["÷"], SymbolReading::new("ワル", 1)?);
+        insert(&["％", "%"], SymbolReading::new("パーセント", 3)?);
+        insert(&["＄", "$"], SymbolReading::new("ドル", 1)?);
+        insert(&["￥", "¥"], SymbolReading::new("エン", 1)?);
+        insert(&["℃", "°"], SymbolReading::new("ド", 1)?);
+        insert(
+            &["＃", "#"],
+            SymbolReading::new("シャープ", 2)?.before_number("バン", 1)?,
+        );
+        insert(&["＠", "@"], SymbolReading::new("アット", 1)?);
+        insert(&["〒"], SymbolReading::new("ユービン", 0)?);
+        insert(&["♪", "♫", "♬"], SymbolReading::new("", 0)?);
+        insert(&["…", "‥"], SymbolReading::new("、", 0)?);
+
+        Ok(table)
+    }
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        Self::try_default().expect("Failed to build default symbol table")
+    }
+}
+
+/// Sets the pronunciation of the symbols (POS::Kigou) which have no pronunciation or are read as pause.
+pub(crate) fn njd_set_symbol_pronunciation(njd: &mut NJD, table: &SymbolTable) {
+    for i in 0..njd.nodes.len() {
+        let node = &njd.nodes[i];
+        if !matches!(node.get_pos(), POS::Kigou(_))
+            || !(node.get_pron().is_empty() || node.get_pron().is_touten())
+        {
+            continue;
+        }
+        let Some(reading) = table.get(node.get_string()) else {
+            continue;
+        };
+        let (pron, acc) = reading.select(&njd.nodes, i);
+
+        let node = &mut njd.nodes[i];
+        if pron.is_empty() {
+            node.unset_pron();
+            node.unset_read();
+        } else {
+            node.set_read(&pron.to_pure_string());
+            node.set_mora_size(pron.mora_size() as i32);
+            node.set_acc(*acc);
+            node.set_pron(pron.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{NJDNode, NJD};
+
+    use super::{njd_set_symbol_pronunciation, SymbolReading, SymbolTable};
+
+    fn prons(nodes: &[&str], table: &SymbolTable) -> Vec<String> {
+        let mut njd = NJD {
+            nodes: nodes.iter().map(|node| NJDNode::new_single(node)).collect(),
+        };
+        njd_set_symbol_pronunciation(&mut njd, table);
+        njd.nodes
+            .iter()
+            .map(|node| node.get_pron().to_string())
+            .collect()
+    }
+
+    const ZERO: &str = "０,名詞,数,*,*,*,*,０,ゼロ,ゼロ,1/2,C3";
+    const ONE: &str = "１,名詞,数,*,*,*,*,１,イチ,イチ,2/2,C3";
+    const TWO: &str = "２,名詞,数,*,*,*,*,２,ニ,ニ,1/1,C3";
+    const THREE: &str = "３,名詞,数,*,*,*,*,３,サン,サン,0/2,C3";
+    const FOUR: &str = "４,名詞,数,*,*,*,*,４,ヨン,ヨン,1/2,C3";
+    const FIVE: &str = "５,名詞,数,*,*,*,*,５,ゴ,ゴ,1/1,C3";
+    const SIX: &str = "６,名詞,数,*,*,*,*,６,ロク,ロク,2/2,C3";
+    const SEVEN: &str = "７,名詞,数,*,*,*,*,７,ナナ,ナナ,1/2,C3";
+    const EIGHT: &str = "８,名詞,数,*,*,*,*,８,ハチ,ハチ,2/2,C3";
+    const MINUS: &str = "−,記号,一般,*,*,*,*,−,、,、,*/*,*";
+    const PLUS: &str = "＋,記号,一般,*,*,*,*,＋,、,、,*/*,*";
+    const NOTE: &str = "※,記号,一般,*,*,*,*,※,、,、,*/*,*";
+    const EMPTY: &str = ",記号,空白,*,*,*,*,,、,、,*/*,*";
+
+    #[test]
+    fn context() {
+        let table = SymbolTable::default();
+        assert_eq!(prons(&[THREE, MINUS, FIVE], &table)[1], "カラ");
+        assert_eq!(prons(&[MINUS, FIVE], &table)[0], "マイナス");
+        assert_eq!(prons(&[NOTE, MINUS], &table), ["コメジルシ", "ハイフン"]);
+        // an empty node is not a number
+        assert_eq!(prons(&[EMPTY, MINUS, FIVE], &table)[1], "マイナス");
+        assert_eq!(
+            prons(&[THREE, MINUS, FIVE, MINUS, THREE], &table),
+            ["サン", "ハイフン", "ゴ", "ハイフン", "サン"]
+        );
+        // naist-jdic splits a number into digits
+        assert_eq!(
+            prons(
+                &[ZERO, THREE, MINUS, ONE, TWO, THREE, FOUR, MINUS, FIVE, SIX, SEVEN, EIGHT],
+                &table
+            ),
+            [
+                "ゼロ",
+                "サン",
+                "ハイフン",
+                "イチ",
+                "ニ",
+                "サン",
+                "ヨン",
+                "ハイフン",
+                "ゴ",
+                "ロク",
+                "ナナ",
+                "ハチ"
+            ]
+        );
+        assert_eq!(prons(&[ONE, TWO, MINUS, ONE, FIVE], &table)[2], "カラ");
+        assert_eq!(prons(&[THREE, PLUS, FIVE], &table)[1], "タス");
+        assert_eq!(prons(&[PLUS, FIVE], &table)[0], "プラス");
+    }
+
+    #[test]
+    fn overrides() {
+        let mut table = SymbolTable::default();
+        table.insert("※", SymbolReading::new("チューイ", 1).unwrap());
+        table.insert("＋", SymbolReading::new("", 0).unwrap());
+        table.remove("−");
+        assert_eq!(prons(&[NOTE, PLUS, MINUS], &table), ["チューイ", "", "、"]);
+
+        assert!(SymbolReading::new("abcア", 0).is_err());
+    }
+}
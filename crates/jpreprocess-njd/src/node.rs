@@ -12,6 +12,7 @@ use jpreprocess_core::accent_rule::ChainRule;
 pub struct NJDNode {
     string: String, //*は空文字列として扱う
     details: WordDetails,
+    numerical_reading: Option<bool>,
 }
 
 impl Display for NJDNode {
@@ -50,7 +51,11 @@ impl NJDNode {
         entry
             .get_with_string(string)
             .into_iter()
-            .map(|(string, details)| Self { string, details })
+            .map(|(string, details)| Self {
+                string,
+                details,
+                numerical_reading: None,
+            })
             .collect()
     }
 
@@ -137,6 +142,17 @@ impl NJDNode {
         }
     }
 
+    /// Whether the digits are read as a number (e.g. ヒャクニジュウサン) rather than
+    /// one by one (e.g. イチニーサン).
+    ///
+    /// If `None`, it is estimated from the context in [`njd_set_digit`](crate::digit::njd_set_digit).
+    pub fn get_numerical_reading(&self) -> Option<bool> {
+        self.numerical_reading
+    }
+    pub fn set_numerical_reading(&mut self, numerical_reading: bool) {
+        self.numerical_reading = Some(numerical_reading);
+    }
+
     pub fn set_pron_by_str(&mut self, pron: &str) {
         self.details.pron = Pronunciation::from_str(pron).unwrap();
     }
@@ -0,0 +1,51 @@
+#!/usr/bin/env python3
+"""Generates src/normalize_text/emoji_ja.tsv from the Unicode CLDR data.
+
+Usage:
+    git clone --depth 1 --branch release-44 https://github.com/unicode-org/cldr.git
+    python3 scripts/emoji_ja.py cldr > src/normalize_text/emoji_ja.tsv
+
+The `type="tts"` entries of common/annotations/ja.xml and common/annotationsDerived/ja.xml
+are written as `emoji<TAB>name`, in the order of the code points.
+"""
+
+import sys
+import xml.etree.ElementTree as ET
+from pathlib import Path
+
+CLDR_VERSION = "44"
+FILES = ["common/annotations/ja.xml", "common/annotationsDerived/ja.xml"]
+
+
+def read_names(path: Path) -> dict[str, str]:
+    names = {}
+    for annotation in ET.parse(path).getroot().iter("annotation"):
+        if annotation.get("type") != "tts":
+            continue
+        name = (annotation.text or "").strip()
+        # `↑↑↑` means that the value is inherited from the parent locale
+        if not name or name == "↑↑↑" or "\t" in name or "\n" in name:
+            continue
+        names[annotation.get("cp")] = name
+    return names
+
+
+def main() -> None:
+    if len(sys.argv) != 2:
+        sys.exit(f"usage: {sys.argv[0]} <path to CLDR {CLDR_VERSION}>")
+    cldr = Path(sys.argv[1])
+
+    names: dict[str, str] = {}
+    for file in FILES:
+        for emoji, name in read_names(cldr / file).items():
+            names.setdefault(emoji, name)
+
+    print(f"# Japanese names of emoji, generated by scripts/emoji_ja.py from Unicode CLDR {CLDR_VERSION}")
+    print("# (the type=\"tts\" entries of common/annotations/ja.xml and common/annotationsDerived/ja.xml).")
+    print("# Unicode License v3, see NOTICE. Do not edit by hand.")
+    for emoji in sorted(names, key=lambda emoji: [ord(c) for c in emoji]):
+        print(f"{emoji}\t{names[emoji]}")
+
+
+if __name__ == "__main__":
+    main()
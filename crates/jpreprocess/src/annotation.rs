@@ -0,0 +1,186 @@
+use jpreprocess_core::JPreprocessResult;
+
+use crate::{ruby::parse_reading, ssml::markup_error};
+
+/// The mark of the accent nucleus in the reading of [`AnnotatedSegment::Word`].
+pub const ACCENT_MARK: char = '\'';
+
+/// A part of text with inline annotations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnnotatedSegment {
+    Text(String),
+    /// `{日本/ニ'ホン}`, the text read as `reading` with the accent `acc`.
+    Word {
+        text: String,
+        reading: String,
+        acc: i32,
+    },
+    /// `|`, the boundary of accent phrases.
+    AccentPhraseBoundary,
+    /// `/`, a pause without punctuation.
+    Pause,
+}
+
+/// Parses the text with inline annotations.
+///
+/// - `{日本/ニ'ホン}` reads the word as the reading in hiragana or katakana.
+///   The accent nucleus is just before `'`, and the accent is flat (0) if the reading has no `'`.
+///   The word is a separate accent phrase.
+/// - `|` is the boundary of accent phrases.
+/// - `/` is a pause, as well as `、` in the text.
+/// - `\` escapes the next character, e.g. `\/` for `/`.
+///
+/// ```rust
+/// use jpreprocess::{parse_annotation, AnnotatedSegment};
+///
+/// let segments = parse_annotation("{日本/ニ'ホン}の|首都/東京")?;
+/// assert_eq!(
+///     segments,
+///     [
+///         AnnotatedSegment::Word {
+///             text: "日本".to_string(),
+///             reading: "ニホン".to_string(),
+///             acc: 1
+///         },
+///         AnnotatedSegment::Text("の".to_string()),
+///         AnnotatedSegment::AccentPhraseBoundary,
+///         AnnotatedSegment::Text("首都".to_string()),
+///         AnnotatedSegment::Pause,
+///         AnnotatedSegment::Text("東京".to_string()),
+///     ]
+/// );
+/// # Ok::<(), jpreprocess_core::JPreprocessError>(())
+/// ```
+pub fn parse_annotation(text: &str) -> JPreprocessResult<Vec<AnnotatedSegment>> {
+    let mut segments = vec![];
+    let mut plain = String::new();
+    // the text of the word being annotated
+    let mut word: Option<String> = None;
+
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped) => word.as_mut().unwrap_or(&mut plain).push(escaped),
+                None => return Err(markup_error("Nothing to escape after \\".to_string())),
+            },
+            '{' if word.is_none() => {
+                push_text(&mut segments, &mut plain);
+                word = Some(String::new());
+            }
+            '}' => {
+                let word = word
+                    .take()
+                    .ok_or_else(|| markup_error("} does not have {".to_string()))?;
+                segments.push(parse_word(&word)?);
+            }
+            '|' if word.is_none() => {
+                push_text(&mut segments, &mut plain);
+                segments.push(AnnotatedSegment::AccentPhraseBoundary);
+            }
+            '/' if word.is_none() => {
+                push_text(&mut segments, &mut plain);
+                segments.push(AnnotatedSegment::Pause);
+            }
+            '{' | '|' => return Err(markup_error(format!("{} is not allowed in {{}}", c))),
+            _ => word.as_mut().unwrap_or(&mut plain).push(c),
+        }
+    }
+    if let Some(word) = word {
+        return Err(markup_error(format!("{{{} is not closed", word)));
+    }
+    push_text(&mut segments, &mut plain);
+
+    Ok(segments)
+}
+
+fn push_text(segments: &mut Vec<AnnotatedSegment>, plain: &mut String) {
+    if !plain.is_empty() {
+        segments.push(AnnotatedSegment::Text(std::mem::take(plain)));
+    }
+}
+
+/// Parses `日本/ニ'ホン` in `{日本/ニ'ホン}`.
+fn parse_word(word: &str) -> JPreprocessResult<AnnotatedSegment> {
+    let (text, reading) = word
+        .split_once('/')
+        .filter(|(text, _)| !text.is_empty())
+        .ok_or_else(|| markup_error(format!("{{{}}} must be {{text/reading}}", word)))?;
+
+    let acc = match reading.split_once(ACCENT_MARK) {
+        Some((_, after)) if after.contains(ACCENT_MARK) => {
+            return Err(markup_error(format!(
+                "{} has more than one accent nucleus",
+                reading
+            )));
+        }
+        Some((before, _)) => parse_reading(before)?.mora_size() as i32,
+        None => 0,
+    };
+    let reading: String = reading.chars().filter(|c| *c != ACCENT_MARK).collect();
+    parse_reading(&reading)?;
+
+    Ok(AnnotatedSegment::Word {
+        text: text.to_string(),
+        reading,
+        acc,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use jpreprocess_core::error::JPreprocessErrorKind;
+
+    use super::{parse_annotation, AnnotatedSegment};
+
+    fn word(text: &str, reading: &str, acc: i32) -> AnnotatedSegment {
+        AnnotatedSegment::Word {
+            text: text.to_string(),
+            reading: reading.to_string(),
+            acc,
+        }
+    }
+
+    #[test]
+    fn words() {
+        assert_eq!(
+            parse_annotation("{箸/ハシ'}と{橋/はし}{端/ハ'シ}").unwrap(),
+            [
+                word("箸", "ハシ", 2),
+                AnnotatedSegment::Text("と".to_string()),
+                word("橋", "はし", 0),
+                word("端", "ハシ", 1),
+            ]
+        );
+        assert_eq!(
+            parse_annotation(r"1\/2\|\{").unwrap(),
+            [AnnotatedSegment::Text("1/2|{".to_string())]
+        );
+        assert_eq!(
+            parse_annotation("/|").unwrap(),
+            [
+                AnnotatedSegment::Pause,
+                AnnotatedSegment::AccentPhraseBoundary
+            ]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        for text in [
+            "{日本/ニホン",
+            "日本}",
+            "{日本}",
+            "{/ニホン}",
+            "{日本/ニ'ホ'ン}",
+            "{日{本/ニホン}",
+            "{日本/ニホン|}",
+            "末尾\\",
+        ] {
+            let err = parse_annotation(text).unwrap_err();
+            assert_eq!(err.kind, JPreprocessErrorKind::MarkupParseError, "{}", text);
+        }
+        let err = parse_annotation("{日本/nihon}").unwrap_err();
+        assert_eq!(err.kind, JPreprocessErrorKind::PronunciationParseError);
+    }
+}
@@ -1,12 +1,28 @@
-use std::{error::Error, fs::File, io::Write, ops::Deref, path::PathBuf};
+use std::{
+    collections::HashSet,
+    error::Error,
+    fs::File,
+    io::Write,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
 
 use clap::{Parser, Subcommand, ValueEnum};
 use jpreprocess::SystemDictionaryConfig;
 use jpreprocess_core::error::JPreprocessErrorKind;
+use jpreprocess_dictionary::WordDictionaryMode;
 use jpreprocess_dictionary_builder::{
-    to_csv::dict_to_csv,
-    ipadic_builder::IpadicBuilder,
+    diff::{diff_rows, DiffEntry},
+    ipadic_builder::{
+        read_csv_rows, read_dict_csv_rows, user_right_id_path, IpadicBuilder, RIGHT_ID_FILE,
+    },
+    lint::lint_csv,
+    lite_builder::{LiteBuilder, PruneConfig},
+    merge::merge_rows,
+    query::EntryLookup,
     serializer::{DictionarySerializer, JPreprocessSerializer, LinderaSerializer},
+    to_csv::dict_to_csv_with_right_ids,
+    unidic_builder::UnidicBuilder,
 };
 use lindera_core::dictionary_builder::DictionaryBuilder;
 use lindera_dictionary::{load_user_dictionary, UserDictionaryConfig};
@@ -26,8 +42,17 @@ struct Cli {
 enum Commands {
     Inspect {
         /// The Word id to display
-        #[arg(short, long)]
+        #[arg(short, long, group = "query")]
         word_id: Option<u32>,
+        /// Display all entries with this surface
+        #[arg(short, long, group = "query")]
+        surface: Option<String>,
+        /// Display all entries whose surface starts with this string
+        #[arg(short, long, group = "query")]
+        prefix: Option<String>,
+        /// Display all entries with this reading or pronunciation
+        #[arg(short, long, group = "query")]
+        reading: Option<String>,
 
         input: PathBuf,
     },
@@ -35,6 +60,9 @@ enum Commands {
         /// User dictionary
         #[arg(short, long)]
         user: bool,
+        /// The input is UniDic instead of IPADIC/naist-jdic
+        #[arg(long)]
+        unidic: bool,
         /// The serlializer to be used
         #[arg(value_enum)]
         serializer: Serializer,
@@ -58,6 +86,83 @@ enum Commands {
         /// The path to the output csv file
         output: PathBuf,
     },
+    /// Check every row of a dictionary csv and report all problems found
+    Lint {
+        /// The output format
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+
+        /// The csv file to be checked
+        input: PathBuf,
+    },
+    /// Compare two dictionaries and report added, removed and changed entries
+    ///
+    /// Each dictionary may be a built system dictionary (directory),
+    /// a built user dictionary (.bin) or a dictionary csv (.csv).
+    /// Entries are matched by surface, POS, ctype and cform.
+    Diff {
+        /// The old dictionary
+        old: PathBuf,
+        /// The new dictionary
+        new: PathBuf,
+    },
+    /// Apply override csv files to a base dictionary and build a new dictionary
+    ///
+    /// Entries in the base dictionary are replaced if an override has an entry
+    /// with the same surface, POS, ctype and cform.
+    Merge {
+        /// User dictionary
+        #[arg(short, long)]
+        user: bool,
+        /// The serlializer to be used
+        #[arg(value_enum)]
+        serializer: Serializer,
+
+        /// The source directory(system dictionary) or csv file(user dictionary) to start with.
+        base: PathBuf,
+        /// The directory(system dictionary) or file(user dictionary) to put the dictionary.
+        /// If the extension is csv, the merged csv is written instead.
+        output: PathBuf,
+
+        /// The csv files to be applied, in order
+        #[arg(short = 'o', long = "override", required = true)]
+        overrides: Vec<PathBuf>,
+    },
+    /// Build a smaller system dictionary, dropping the entries not needed
+    Prune {
+        /// The serlializer to be used
+        #[arg(value_enum)]
+        serializer: Serializer,
+
+        /// The source directory of IPADIC/naist-jdic
+        input: PathBuf,
+        /// The directory to put the dictionary
+        output: PathBuf,
+
+        /// Drop the entries whose cost is higher than this
+        #[arg(long, allow_negative_numbers = true)]
+        max_cost: Option<i16>,
+        /// Drop the entries with this POS, e.g. `名詞,固有名詞,人名`
+        #[arg(long)]
+        deny_pos: Vec<String>,
+        /// A file of surfaces, one per line, to be kept regardless of the other rules
+        #[arg(long)]
+        allow: Option<PathBuf>,
+        /// A file of surfaces, one per line, to be dropped
+        #[arg(long)]
+        deny: Option<PathBuf>,
+        /// Merge the context ids with the same connection costs to shrink the matrix
+        #[arg(long)]
+        merge_context_ids: bool,
+    },
+}
+
+#[derive(Clone, ValueEnum, Debug)]
+enum OutputFormat {
+    /// Human readable text
+    Text,
+    /// JSON array of issues
+    Json,
 }
 
 #[derive(Clone, ValueEnum, Debug)]
@@ -72,7 +177,13 @@ fn main() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Inspect { word_id, input } => {
+        Commands::Inspect {
+            word_id,
+            surface,
+            prefix,
+            reading,
+            input,
+        } => {
             let is_system_dict = input.is_dir()
                 && input.join("dict.wordsidx").exists()
                 && input.join("dict.words").exists();
@@ -106,19 +217,46 @@ fn main() -> Result<(), Box<dyn Error>> {
 
                     let word = mode.debug_get_word(&query);
                     println!("{}", word);
+                    return Ok(());
                 }
+
+                let serializer = mode_serializer(&dict);
+                let (prefix_dict, words_idx_data, words_data) = dict.dictionary_data();
+                let lookup =
+                    EntryLookup::new(prefix_dict, words_idx_data, words_data, serializer.deref());
+
+                let entries = if let Some(surface) = surface {
+                    lookup.surface(&surface)?
+                } else if let Some(prefix) = prefix {
+                    lookup.prefix(&prefix)?
+                } else if let Some(reading) = reading {
+                    lookup.reading(&reading)?
+                } else {
+                    return Ok(());
+                };
+
+                for entry in &entries {
+                    println!("{}", entry);
+                }
+                println!("{} entries found.", entries.len());
             }
         }
         Commands::Build {
             user,
+            unidic,
             serializer: serlializer,
             input,
             output,
         } => {
-            let builder = IpadicBuilder::new(match serlializer {
+            let serializer: Box<dyn DictionarySerializer + Send + Sync> = match serlializer {
                 Serializer::Lindera => Box::new(LinderaSerializer),
                 Serializer::Jpreprocess => Box::new(JPreprocessSerializer),
-            });
+            };
+            let builder: Box<dyn DictionaryBuilder> = if unidic {
+                Box::new(UnidicBuilder::new(serializer))
+            } else {
+                Box::new(IpadicBuilder::new(serializer))
+            };
 
             if user {
                 println!("Building user dictionary...");
@@ -147,6 +285,7 @@ fn main() -> Result<(), Box<dyn Error>> {
             }
 
             println!("Loading dictionary...");
+            let right_id_data = read_right_ids(&input, user);
             let dict = if !user {
                 let dict = SystemDictionaryConfig::File(input).load()?;
                 QueryDict::System(dict)
@@ -158,6 +297,9 @@ fn main() -> Result<(), Box<dyn Error>> {
                 QueryDict::User(dict)
             };
             println!("Successfully loaded source dictionary.");
+            if right_id_data.is_none() {
+                println!("No right_id table found. Assuming right_id is the same as left_id.");
+            }
 
             let serializer: Box<dyn DictionarySerializer> = match serializer_config {
                 Serializer::Lindera => Box::new(LinderaSerializer),
@@ -167,8 +309,13 @@ fn main() -> Result<(), Box<dyn Error>> {
             let (prefix_dict, words_idx_data, words_data) = dict.dictionary_data();
 
             println!("Converting dictionary csv...");
-            let csv =
-                dict_to_csv(prefix_dict, words_idx_data, words_data, serializer.deref())?;
+            let csv = dict_to_csv_with_right_ids(
+                prefix_dict,
+                words_idx_data,
+                words_data,
+                right_id_data.as_deref(),
+                serializer.deref(),
+            )?;
             println!("done.");
 
             println!("Writing csv file...");
@@ -177,7 +324,174 @@ fn main() -> Result<(), Box<dyn Error>> {
             file.flush()?;
             println!("done.");
         }
+        Commands::Lint { format, input } => {
+            let issues = lint_csv(&input)?;
+
+            match format {
+                OutputFormat::Text => {
+                    for issue in &issues {
+                        println!("{}:{}", input.display(), issue);
+                    }
+                    println!("{} problem(s) found.", issues.len());
+                }
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&issues)?),
+            }
+
+            if !issues.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Commands::Diff { old, new } => {
+            let diff = diff_rows(&load_rows(&old)?, &load_rows(&new)?);
+
+            let (mut added, mut removed, mut changed) = (0, 0, 0);
+            for entry in &diff {
+                match entry {
+                    DiffEntry::Added(_) => added += 1,
+                    DiffEntry::Removed(_) => removed += 1,
+                    DiffEntry::Changed(_, _) => changed += 1,
+                }
+                println!("{}", entry);
+            }
+            println!("{} added, {} removed, {} changed.", added, removed, changed);
+        }
+        Commands::Merge {
+            user,
+            serializer,
+            base,
+            output,
+            overrides,
+        } => {
+            let base_rows = if user {
+                read_csv_rows(&base)?
+            } else {
+                read_dict_csv_rows(&base)?
+            };
+            let override_rows = overrides
+                .iter()
+                .map(|path| read_csv_rows(path))
+                .collect::<Result<Vec<_>, _>>()?;
+            let rows = merge_rows(base_rows, &override_rows);
+
+            if matches!(output.extension(),Some(s) if s.to_str()==Some("csv")) {
+                println!("Writing csv file...");
+                let mut file = File::create(output)?;
+                let csv: Vec<String> = rows.iter().map(|row| row.join(",")).collect();
+                file.write_all(csv.join("\n").as_bytes())?;
+                file.flush()?;
+                println!("done.");
+                return Ok(());
+            }
+
+            let builder = IpadicBuilder::new(match serializer {
+                Serializer::Lindera => Box::new(LinderaSerializer),
+                Serializer::Jpreprocess => Box::new(JPreprocessSerializer),
+            });
+
+            if user {
+                println!("Building user dictionary...");
+                builder.build_user_dictionary_from_rows(&rows, &output)?;
+                println!("done.");
+            } else {
+                println!("Building system dictionary...");
+                builder.build_dictionary_from_rows(&base, &rows, &output)?;
+                println!("done.");
+            }
+        }
+        Commands::Prune {
+            serializer,
+            input,
+            output,
+            max_cost,
+            deny_pos,
+            allow,
+            deny,
+            merge_context_ids,
+        } => {
+            let config = PruneConfig {
+                max_cost,
+                deny_pos,
+                allow: allow
+                    .map(|path| read_lines(&path))
+                    .transpose()?
+                    .unwrap_or_default(),
+                deny: deny
+                    .map(|path| read_lines(&path))
+                    .transpose()?
+                    .unwrap_or_default(),
+                merge_context_ids,
+            };
+            let builder = LiteBuilder::new(
+                match serializer {
+                    Serializer::Lindera => Box::new(LinderaSerializer),
+                    Serializer::Jpreprocess => Box::new(JPreprocessSerializer),
+                },
+                config,
+            );
+
+            println!("Building system dictionary...");
+            let summary = builder.build_dictionary(&input, &output)?;
+            println!("{}", summary);
+            println!("done.");
+        }
     }
 
     Ok(())
 }
+
+/// Loads the rows of a built dictionary or a dictionary csv.
+fn load_rows(input: &Path) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+    let right_id_data = read_right_ids(input, !input.is_dir());
+    let dict = if input.is_dir() {
+        QueryDict::System(SystemDictionaryConfig::File(input.to_path_buf()).load()?)
+    } else if matches!(input.extension(),Some(s) if s.to_str()==Some("bin")) {
+        QueryDict::User(load_user_dictionary(UserDictionaryConfig {
+            path: input.to_path_buf(),
+            kind: None,
+        })?)
+    } else {
+        return Ok(read_csv_rows(input)?);
+    };
+
+    let serializer = mode_serializer(&dict);
+    let (prefix_dict, words_idx_data, words_data) = dict.dictionary_data();
+    let csv = dict_to_csv_with_right_ids(
+        prefix_dict,
+        words_idx_data,
+        words_data,
+        right_id_data.as_deref(),
+        serializer.deref(),
+    )?;
+
+    Ok(csv
+        .iter()
+        .map(|row| row.split(',').map(String::from).collect())
+        .collect())
+}
+
+/// Reads non-empty lines of a file, e.g. a list of surfaces.
+fn read_lines(path: &Path) -> Result<HashSet<String>, Box<dyn Error>> {
+    Ok(std::fs::read_to_string(path)?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+/// Returns the serializer to read the dictionary with.
+fn mode_serializer(dict: &QueryDict) -> Box<dyn DictionarySerializer> {
+    match dict.mode() {
+        WordDictionaryMode::Lindera => Box::new(LinderaSerializer),
+        WordDictionaryMode::JPreprocess => Box::new(JPreprocessSerializer),
+    }
+}
+
+/// Reads the right_id table written along with the dictionary, if any.
+fn read_right_ids(input: &Path, user: bool) -> Option<Vec<u8>> {
+    let path = if user {
+        user_right_id_path(input)
+    } else {
+        input.join(RIGHT_ID_FILE)
+    };
+    std::fs::read(path).ok()
+}
@@ -0,0 +1,423 @@
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use jpreprocess::*;
+
+use clap::{error::ErrorKind, Args, CommandFactory, Parser, ValueEnum};
+use lindera_dictionary::UserDictionaryConfig;
+
+use crate::output::OutputFormat;
+
+mod output;
+
+/// The number of utterances read at once, per job.
+const BATCH_SIZE_PER_JOB: usize = 64;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[command(flatten)]
+    dict: DictionaryArgs,
+
+    /// The location of the user dictionary
+    #[arg(short, long)]
+    user_dictionary: Option<PathBuf>,
+
+    /// The format of the input
+    #[arg(long, value_enum, default_value_t = InputFormat::Text)]
+    input_format: InputFormat,
+
+    /// The files to be processed, or `-` for stdin
+    #[arg(short, long = "input-file", conflicts_with = "input")]
+    input_files: Vec<PathBuf>,
+
+    /// The format of the output
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Fullcontext)]
+    output_format: OutputFormat,
+
+    /// A JSON file with the durations of the phonemes in seconds, which fill the time columns
+    /// of the `fullcontext` and `mono` output.
+    /// The file has an array of the durations for each utterance in order,
+    /// e.g. `[[0.1, 0.05, 0.08], [0.1, 0.12]]`, or an array of the durations for a single utterance.
+    #[arg(long)]
+    durations: Option<PathBuf>,
+
+    /// Write the output into files in this directory instead of stdout
+    #[arg(long)]
+    output_dir: Option<PathBuf>,
+
+    /// How to split the output files in `--output-dir`
+    #[arg(long, value_enum, default_value_t = Split::Utterance, requires = "output_dir")]
+    split: Split,
+
+    /// The number of threads [default: the number of CPUs]
+    #[arg(short, long)]
+    jobs: Option<NonZeroUsize>,
+
+    /// The text to be processed, or `-` to read from stdin.
+    /// If neither this nor `--input-file` is given, the input is read from stdin.
+    input: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum InputFormat {
+    /// Plain text. Each line is an utterance.
+    Text,
+    /// Text with ruby markup (`｜漢字《かんじ》` or `<ruby>`). Each line is an utterance.
+    Ruby,
+    /// Text with inline annotations (`{日本/ニ'ホン}`). Each line is an utterance.
+    Annotated,
+    /// A subset of SSML. Each input is an utterance.
+    Ssml,
+    /// Output of MeCab with an OpenJTalk-compatible dictionary (`surface\tfeatures` lines).
+    /// Each sentence terminated by `EOS` is an utterance.
+    /// The dictionary is not used.
+    Mecab,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Split {
+    /// One file per utterance, named `{input}_{line}.{ext}`
+    Utterance,
+    /// One file per input, named `{input}.{ext}`
+    File,
+}
+
+#[derive(Args, Debug)]
+#[group(required = false, multiple = false)]
+struct DictionaryArgs {
+    /// The location of the system dictionary
+    #[arg(short, long)]
+    dictionary: Option<PathBuf>,
+
+    /// Use bundled naist-jdic dictionary
+    #[cfg(feature = "naist-jdic")]
+    #[arg(short, long)]
+    naist_jdic: bool,
+}
+
+/// An input, which is a text given in the arguments, stdin or a file.
+struct Source {
+    /// The name used in the messages and the output file names
+    name: String,
+    reader: Box<dyn BufRead>,
+}
+
+struct Utterance {
+    /// The line number where the utterance starts, from 1
+    line: usize,
+    text: String,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+
+    let jpreprocess = match cli.input_format {
+        InputFormat::Mecab => None,
+        _ => Some(load_jpreprocess(cli.dict, cli.user_dictionary)?),
+    };
+    let jobs = cli
+        .jobs
+        .or_else(|| std::thread::available_parallelism().ok())
+        .map(NonZeroUsize::get)
+        .unwrap_or(1);
+    if let Some(output_dir) = &cli.output_dir {
+        fs::create_dir_all(output_dir)?;
+    }
+    let durations = match &cli.durations {
+        Some(_) if !cli.output_format.is_lab() => Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--durations can only be used with the fullcontext or mono output",
+            )
+            .exit(),
+        Some(path) => Some(load_durations(path)?),
+        None => None,
+    };
+    // the index of the first utterance of the batch, counted over all the inputs
+    let mut utterance_offset = 0;
+
+    let sources: Vec<Source> = match (cli.input, cli.input_files.is_empty()) {
+        (Some(text), _) if text != "-" => vec![Source {
+            name: "text".to_string(),
+            reader: Box::new(io::Cursor::new(text.into_bytes())),
+        }],
+        (_, true) => vec![stdin()],
+        (_, false) => cli
+            .input_files
+            .iter()
+            .map(|path| {
+                if path == Path::new("-") {
+                    return Ok(stdin());
+                }
+                Ok(Source {
+                    name: path
+                        .file_stem()
+                        .map(|stem| stem.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| "input".to_string()),
+                    reader: Box::new(BufReader::new(File::open(path)?)),
+                })
+            })
+            .collect::<io::Result<_>>()?,
+    };
+
+    let stdout = io::stdout();
+    let mut has_error = false;
+    for source in sources {
+        let mut file_output = match (&cli.output_dir, cli.split) {
+            (Some(output_dir), Split::File) => Some(File::create(output_dir.join(format!(
+                "{}.{}",
+                source.name,
+                cli.output_format.extension()
+            )))?),
+            _ => None,
+        };
+        let name = source.name;
+        let mut utterances = utterances(source.reader, cli.input_format);
+
+        loop {
+            let batch: Vec<Utterance> = utterances
+                .by_ref()
+                .take(BATCH_SIZE_PER_JOB * jobs)
+                .collect::<io::Result<_>>()?;
+            if batch.is_empty() {
+                break;
+            }
+
+            let results = process_parallel(&batch, jobs, |index, utterance| {
+                let mut njd = match (cli.input_format, &jpreprocess) {
+                    (InputFormat::Mecab, _) => NJD::from_mecab(&utterance.text)?,
+                    (_, None) => unreachable!("The dictionary is loaded unless the input is MeCab"),
+                    (InputFormat::Text, Some(jpreprocess)) => {
+                        jpreprocess.text_to_njd(&utterance.text)?
+                    }
+                    (InputFormat::Ruby, Some(jpreprocess)) => {
+                        jpreprocess.ruby_text_to_njd(&utterance.text)?
+                    }
+                    (InputFormat::Annotated, Some(jpreprocess)) => {
+                        jpreprocess.annotated_text_to_njd(&utterance.text)?
+                    }
+                    (InputFormat::Ssml, Some(jpreprocess)) => {
+                        jpreprocess.ssml_to_njd(&utterance.text)?
+                    }
+                };
+                njd.preprocess();
+                let durations = match &durations {
+                    Some(durations) => Some(
+                        durations
+                            .get(utterance_offset + index)
+                            .ok_or("No durations are given for the utterance")?
+                            .as_slice(),
+                    ),
+                    None => None,
+                };
+                Ok(cli.output_format.format(&utterance.text, &njd, durations)?)
+            });
+            utterance_offset += batch.len();
+
+            for (utterance, result) in batch.iter().zip(results) {
+                let output = match result {
+                    Ok(output) => output,
+                    Err(err) => {
+                        eprintln!("{}:{}: {}", name, utterance.line, err);
+                        has_error = true;
+                        continue;
+                    }
+                };
+                match (&cli.output_dir, &mut file_output) {
+                    (_, Some(file)) => file.write_all(output.as_bytes())?,
+                    (Some(output_dir), None) => fs::write(
+                        output_dir.join(format!(
+                            "{}_{:05}.{}",
+                            name,
+                            utterance.line,
+                            cli.output_format.extension()
+                        )),
+                        output,
+                    )?,
+                    (None, None) => {
+                        let mut stdout = stdout.lock();
+                        stdout.write_all(output.as_bytes())?;
+                        if cli.output_format.is_multiline() {
+                            // an empty line between the utterances
+                            writeln!(stdout)?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if has_error {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn stdin() -> Source {
+    Source {
+        name: "stdin".to_string(),
+        reader: Box::new(BufReader::new(io::stdin())),
+    }
+}
+
+/// Splits the input into utterances. The lines are read lazily for the line-based formats.
+fn utterances(
+    mut reader: Box<dyn BufRead>,
+    input_format: InputFormat,
+) -> Box<dyn Iterator<Item = io::Result<Utterance>>> {
+    match input_format {
+        InputFormat::Text | InputFormat::Ruby | InputFormat::Annotated => Box::new(
+            reader
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !matches!(line, Ok(line) if line.trim().is_empty()))
+                .map(|(i, line)| {
+                    Ok(Utterance {
+                        line: i + 1,
+                        text: line?,
+                    })
+                }),
+        ),
+        InputFormat::Ssml => {
+            let mut text = String::new();
+            let result = reader.read_to_string(&mut text);
+            Box::new(
+                std::iter::once(result.map(|_| Utterance { line: 1, text }))
+                    .filter(|utterance| !matches!(utterance, Ok(u) if u.text.trim().is_empty())),
+            )
+        }
+        InputFormat::Mecab => {
+            let mut sentences = vec![];
+            let mut sentence: Option<Utterance> = None;
+            for (i, line) in reader.lines().enumerate() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(err) => return Box::new(std::iter::once(Err(err))),
+                };
+                if line == "EOS" {
+                    sentences.extend(sentence.take().map(Ok));
+                } else if !line.is_empty() {
+                    let sentence = sentence.get_or_insert_with(|| Utterance {
+                        line: i + 1,
+                        text: String::new(),
+                    });
+                    sentence.text.push_str(&line);
+                    sentence.text.push('\n');
+                }
+            }
+            sentences.extend(sentence.map(Ok));
+            Box::new(sentences.into_iter())
+        }
+    }
+}
+
+/// Reads the durations of the phonemes for each utterance from a JSON file.
+fn load_durations(path: &Path) -> Result<Vec<Vec<Duration>>, Box<dyn Error>> {
+    let value: serde_json::Value = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+    let to_durations = |value: &serde_json::Value| -> Result<Vec<Duration>, Box<dyn Error>> {
+        value
+            .as_array()
+            .ok_or("The durations must be an array")?
+            .iter()
+            .map(|seconds| {
+                let seconds = seconds
+                    .as_f64()
+                    .filter(|seconds| *seconds >= 0.0)
+                    .ok_or_else(|| format!("Invalid duration {}", seconds))?;
+                Ok(Duration::from_secs_f64(seconds))
+            })
+            .collect()
+    };
+    match value.as_array() {
+        Some(utterances) if utterances.iter().all(|value| value.is_array()) => {
+            utterances.iter().map(to_durations).collect()
+        }
+        _ => Ok(vec![to_durations(&value)?]),
+    }
+}
+
+/// Processes the utterances in `jobs` threads, and returns the results in order.
+///
+/// `process` takes the index of the utterance in the batch and the utterance.
+fn process_parallel<F>(
+    batch: &[Utterance],
+    jobs: usize,
+    process: F,
+) -> Vec<Result<String, Box<dyn Error + Send + Sync>>>
+where
+    F: Fn(usize, &Utterance) -> Result<String, Box<dyn Error + Send + Sync>> + Sync,
+{
+    if jobs <= 1 || batch.len() <= 1 {
+        return batch
+            .iter()
+            .enumerate()
+            .map(|(index, utterance)| process(index, utterance))
+            .collect();
+    }
+    let chunk_size = (batch.len() + jobs - 1) / jobs;
+    let process = &process;
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = batch
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(chunk_index, chunk)| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .enumerate()
+                        .map(|(index, utterance)| {
+                            process(chunk_index * chunk_size + index, utterance)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("Worker thread panicked"))
+            .collect()
+    })
+}
+
+fn load_jpreprocess(
+    dict: DictionaryArgs,
+    user_dictionary: Option<PathBuf>,
+) -> Result<JPreprocess, Box<dyn Error>> {
+    let dictionary = if let Some(dict) = dict.dictionary {
+        SystemDictionaryConfig::File(dict)
+    } else {
+        #[cfg(feature = "naist-jdic")]
+        if dict.naist_jdic {
+            SystemDictionaryConfig::Bundled(kind::JPreprocessDictionaryKind::NaistJdic)
+        } else {
+            missing_dictionary()
+        }
+        #[cfg(not(feature = "naist-jdic"))]
+        missing_dictionary()
+    };
+
+    let user_dictionary = user_dictionary.map(|user_dict| UserDictionaryConfig {
+        path: user_dict,
+        kind: Some(lindera_dictionary::DictionaryKind::IPADIC),
+    });
+
+    Ok(JPreprocess::from_config(JPreprocessConfig {
+        dictionary,
+        user_dictionary,
+        tokenizer: Default::default(),
+    })?)
+}
+
+fn missing_dictionary() -> ! {
+    Cli::command()
+        .error(
+            ErrorKind::MissingRequiredArgument,
+            "A dictionary is required to process text",
+        )
+        .exit()
+}
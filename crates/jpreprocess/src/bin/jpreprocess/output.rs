@@ -0,0 +1,221 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+use jpreprocess::NJD;
+use jpreprocess_core::JPreprocessResult;
+use jpreprocess_jpcommon::labels_to_lab;
+use serde_json::json;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// NJD features after preprocessing, one node per line
+    Njd,
+    /// Fullcontext labels (HTS `.lab`), one phoneme per line
+    Fullcontext,
+    /// Monophone labels (HTS `.lab`), one phoneme per line
+    Mono,
+    /// Phonemes with the prosodic symbols (`^ $ ? _ # [ ]`) in a line
+    Prosody,
+    /// NJD nodes and fullcontext labels as a JSON object in a line
+    Json,
+    /// Pronunciation in katakana in a line
+    Kana,
+}
+
+impl OutputFormat {
+    /// Whether the output of an utterance has multiple lines.
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Self::Njd | Self::Fullcontext | Self::Mono)
+    }
+
+    /// The extension of the output files.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Fullcontext | Self::Mono => "lab",
+            Self::Json => "json",
+            Self::Njd | Self::Prosody | Self::Kana => "txt",
+        }
+    }
+
+    /// Whether the output is HTS label file, which can have the time columns.
+    pub fn is_lab(&self) -> bool {
+        matches!(self, Self::Fullcontext | Self::Mono)
+    }
+
+    /// Formats the preprocessed NJD of `text`. The result ends with a newline.
+    ///
+    /// `durations` of the phonemes fill the time columns of HTS label file.
+    pub fn format(
+        &self,
+        text: &str,
+        njd: &NJD,
+        durations: Option<&[Duration]>,
+    ) -> JPreprocessResult<String> {
+        let lines: Vec<String> = match self {
+            Self::Njd => njd.nodes.iter().map(|node| node.to_string()).collect(),
+            Self::Fullcontext => lab(fullcontext(njd), durations)?,
+            Self::Mono => lab(
+                jpreprocess_jpcommon::njdnodes_to_monophones(&njd.nodes),
+                durations,
+            )?,
+            Self::Prosody => vec![prosody(&fullcontext(njd)).join(" ")],
+            Self::Json => vec![json(text, njd)],
+            Self::Kana => vec![njd
+                .nodes
+                .iter()
+                .map(|node| node.get_pron().to_pure_string())
+                .collect()],
+        };
+        Ok(lines.iter().map(|line| format!("{}\n", line)).collect())
+    }
+}
+
+fn lab(labels: Vec<String>, durations: Option<&[Duration]>) -> JPreprocessResult<Vec<String>> {
+    Ok(labels_to_lab(labels, durations)?
+        .iter()
+        .map(|line| line.to_string())
+        .collect())
+}
+
+fn fullcontext(njd: &NJD) -> Vec<String> {
+    jpreprocess_jpcommon::njdnodes_to_features(&njd.nodes)
+}
+
+fn json(text: &str, njd: &NJD) -> String {
+    let nodes: Vec<_> = njd
+        .nodes
+        .iter()
+        .map(|node| {
+            let features = node.to_string();
+            let features: Vec<&str> = features.split(',').collect();
+            json!({
+                "string": node.get_string(),
+                "pos": features.get(1..5).map(|pos| pos.join(",")),
+                "ctype": features.get(5),
+                "cform": features.get(6),
+                "read": node.get_read(),
+                "pron": node.get_pron().to_string(),
+                "acc": node.get_acc(),
+                "mora_size": node.get_mora_size(),
+                "chain_flag": node.get_chain_flag(),
+            })
+        })
+        .collect();
+    json!({
+        "text": text,
+        "njd": nodes,
+        "fullcontext": fullcontext(njd),
+    })
+    .to_string()
+}
+
+/// The current phoneme (p3) of a fullcontext label.
+fn phoneme(label: &str) -> &str {
+    label
+        .split_once('-')
+        .and_then(|(_, rest)| rest.split_once('+'))
+        .map(|(phoneme, _)| phoneme)
+        .unwrap_or("")
+}
+
+/// The numeric fields of a fullcontext label, e.g. `a1`, `a2` and `a3` of `/A:a1+a2+a3`.
+fn fields(label: &str, key: &str, separators: &[char]) -> Vec<Option<i32>> {
+    label
+        .split_once(key)
+        .map(|(_, rest)| rest.split('/').next().unwrap_or(""))
+        .unwrap_or("")
+        .split(separators)
+        .map(|field| field.parse().ok())
+        .collect()
+}
+
+/// Converts the labels into the phonemes with the prosodic symbols,
+/// in the same way as `pyopenjtalk_g2p_prosody` of ESPnet.
+///
+/// - `^`, `$` and `?`: the beginning and the end (`?` for questions) of the utterance
+/// - `_`: pause
+/// - `#`: boundary of accent phrases
+/// - `[` and `]`: rise and fall of the pitch
+fn prosody(labels: &[String]) -> Vec<String> {
+    let a = |label: &str| {
+        let fields = fields(label, "/A:", &['+']);
+        (
+            fields.first().copied().flatten(),
+            fields.get(1).copied().flatten(),
+            fields.get(2).copied().flatten(),
+        )
+    };
+
+    let mut symbols = vec![];
+    for (i, label) in labels.iter().enumerate() {
+        let p3 = phoneme(label);
+        match p3 {
+            "sil" => {
+                if i == 0 {
+                    symbols.push("^".to_string());
+                } else if i == labels.len() - 1 {
+                    // e3 is 1 if the last accent phrase is interrogative
+                    let e3 = fields(label, "/E:", &['_', '!']).get(2).copied().flatten();
+                    symbols.push(if e3 == Some(1) { "?" } else { "$" }.to_string());
+                }
+                continue;
+            }
+            "pau" => {
+                symbols.push("_".to_string());
+                continue;
+            }
+            _ => symbols.push(p3.to_string()),
+        }
+
+        let (a1, a2, a3) = a(label);
+        let f1 = fields(label, "/F:", &['_']).first().copied().flatten();
+        let a2_next = labels.get(i + 1).and_then(|next| a(next).1);
+
+        if a3 == Some(1)
+            && a2_next == Some(1)
+            && matches!(
+                p3,
+                "a" | "i" | "u" | "e" | "o" | "A" | "I" | "U" | "E" | "O" | "N" | "cl"
+            )
+        {
+            symbols.push("#".to_string());
+        } else if a1 == Some(0) && a2_next.is_some() && a2_next == a2.map(|a2| a2 + 1) && a2 != f1 {
+            symbols.push("]".to_string());
+        } else if a2 == Some(1) && a2_next == Some(2) {
+            symbols.push("[".to_string());
+        }
+    }
+    symbols
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use jpreprocess::{JPreprocess, JPreprocessConfig, SystemDictionaryConfig};
+
+    use super::{fullcontext, phoneme, prosody};
+
+    #[test]
+    fn prosody_symbols() {
+        let jpreprocess = JPreprocess::from_config(JPreprocessConfig {
+            dictionary: SystemDictionaryConfig::File(PathBuf::from("tests/min-dict")),
+            user_dictionary: None,
+            tokenizer: Default::default(),
+        })
+        .unwrap();
+        let mut njd = jpreprocess.annotated_text_to_njd("音声/合成").unwrap();
+        njd.preprocess();
+        let labels = fullcontext(&njd);
+
+        let phonemes: Vec<&str> = labels.iter().map(|label| phoneme(label)).collect();
+        assert_eq!(
+            phonemes,
+            ["sil", "o", "N", "s", "e", "e", "pau", "g", "o", "o", "s", "e", "e", "sil"]
+        );
+        assert_eq!(
+            prosody(&labels).join(" "),
+            "^ o ] N s e e _ g o [ o s e e $"
+        );
+    }
+}
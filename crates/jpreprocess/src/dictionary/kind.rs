@@ -1,19 +1,95 @@
-use lindera_core::dictionary::Dictionary;
+use jpreprocess_core::{error::JPreprocessErrorKind, JPreprocessResult};
+use lindera_core::{dictionary::Dictionary, LinderaResult};
 
 /// Specifies the kind of self-contained dictionary used for tokenization and preprocessing.
 pub enum JPreprocessDictionaryKind {
     #[cfg(feature = "naist-jdic")]
     NaistJdic,
+    /// Dictionary bundled in another crate.
+    ///
+    /// Such a crate can be made in the same way as jpreprocess-naist-jdic;
+    /// build the dictionary in build.rs, embed it and expose the loader function.
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// use std::path::PathBuf;
+    ///
+    /// use jpreprocess::{kind::JPreprocessDictionaryKind, *};
+    /// use lindera_core::{dictionary::Dictionary, LinderaResult};
+    /// use lindera_dictionary::{load_dictionary_from_config, DictionaryConfig};
+    ///
+    /// // The crate would load the embedded dictionary here.
+    /// fn load_dictionary() -> LinderaResult<Dictionary> {
+    ///     load_dictionary_from_config(DictionaryConfig {
+    ///         kind: None,
+    ///         path: Some(PathBuf::from("tests/min-dict")),
+    ///     })
+    /// }
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let kind = JPreprocessDictionaryKind::Custom {
+    ///     name: "min-dict",
+    ///     load: load_dictionary,
+    /// };
+    /// assert_eq!(kind.name(), "min-dict");
+    ///
+    /// let jpreprocess = JPreprocess::from_config(JPreprocessConfig {
+    ///     dictionary: SystemDictionaryConfig::Bundled(kind),
+    ///     user_dictionary: None,
+    ///     tokenizer: Default::default(),
+    /// })?;
+    /// let njd = jpreprocess.text_to_njd("音声合成")?;
+    /// assert_eq!(njd.nodes.len(), 2);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    Custom {
+        name: &'static str,
+        load: fn() -> LinderaResult<Dictionary>,
+    },
 }
 
 impl JPreprocessDictionaryKind {
-    pub(crate) fn load(&self) -> Dictionary {
-        match &self {
+    pub fn name(&self) -> &'static str {
+        match self {
             #[cfg(feature = "naist-jdic")]
-            Self::NaistJdic => jpreprocess_naist_jdic::lindera::load_dictionary().unwrap(),
-
-            #[allow(unreachable_patterns)]
-            _ => unreachable!(),
+            Self::NaistJdic => "naist-jdic",
+            Self::Custom { name, .. } => name,
         }
     }
+
+    pub(crate) fn load(&self) -> JPreprocessResult<Dictionary> {
+        let dictionary = match self {
+            #[cfg(feature = "naist-jdic")]
+            Self::NaistJdic => jpreprocess_naist_jdic::lindera::load_dictionary(),
+            Self::Custom { load, .. } => load(),
+        };
+        dictionary.map_err(|err| {
+            JPreprocessErrorKind::DictionaryLoadError.with_error(anyhow::anyhow!(
+                "Failed to load bundled dictionary {}: {}",
+                self.name(),
+                err
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jpreprocess_core::error::JPreprocessErrorKind;
+    use lindera_core::error::LinderaErrorKind;
+
+    use super::JPreprocessDictionaryKind;
+
+    #[test]
+    fn custom_load_error() {
+        let kind = JPreprocessDictionaryKind::Custom {
+            name: "broken",
+            load: || Err(LinderaErrorKind::Io.with_error(anyhow::anyhow!("not found"))),
+        };
+        let err = kind.load().err().unwrap();
+        assert_eq!(err.kind, JPreprocessErrorKind::DictionaryLoadError);
+        assert!(err.to_string().contains("broken"));
+    }
 }
@@ -8,21 +8,21 @@ pub mod kind;
 
 /// System dictionary configuration for JPreprocess.
 pub enum SystemDictionaryConfig {
-    /// Use self-contained dictionary. This is only valid if appropreate feature is enabled.
+    /// Use self-contained dictionary, either enabled by a feature (e.g. naist-jdic) or bundled in another crate.
     Bundled(kind::JPreprocessDictionaryKind),
     /// Use pre-built external lindera/jpreprocess dictionary. The PathBuf is the path to dictionary.
     ///
     /// - When you are using lindera dictionary: Normal dictionary cannot be used;
     ///   it must contain the accent position and accent rule.
-    /// - When you are using jpreprocess dictionary: The JPreprocess version needs to be same as the
-    ///   JPreprocess that built the dictionary.
+    /// - When you are using jpreprocess dictionary: The dictionary needs to be built by
+    ///   the same or older version of JPreprocess.
     File(PathBuf),
 }
 
 impl SystemDictionaryConfig {
     pub fn load(self) -> JPreprocessResult<Dictionary> {
         match self {
-            Self::Bundled(kind) => Ok(kind.load()),
+            Self::Bundled(kind) => kind.load(),
             Self::File(dictionary_path) => load_dictionary_from_config(DictionaryConfig {
                 kind: None,
                 path: Some(dictionary_path),
@@ -12,6 +12,7 @@
 //!  let config = JPreprocessConfig {
 //!      dictionary: SystemDictionaryConfig::File(path),
 //!      user_dictionary: None,
+//!      tokenizer: Default::default(),
 //!  };
 //! let jpreprocess = JPreprocess::from_config(config)?;
 //!
@@ -39,29 +40,72 @@
 //! # }
 //! ```
 
+mod annotation;
 mod dictionary;
 mod normalize_text;
+mod ruby;
+mod sentence;
+mod ssml;
+pub mod tokenizer;
 
+pub use annotation::{parse_annotation, AnnotatedSegment, ACCENT_MARK};
 pub use dictionary::*;
-pub use normalize_text::normalize_text_for_naist_jdic;
+pub use normalize_text::{
+    normalize_text_for_naist_jdic, EmojiHandling, EmojiReader, NormalizeStep, OffsetMap,
+    ReplaceRules, TextNormalizer, WebTextConversion, WebTextKind, WebTextReader, WebTextReading,
+    WebTextSpan,
+};
+
+pub use ruby::{parse_ruby, RubySegment};
+pub use sentence::split_sentences;
+pub use ssml::{parse_ssml, SayAs, SsmlSegment, KANA_ALPHABET};
 
 pub use jpreprocess_core::error;
-pub use jpreprocess_njd::NJD;
+pub use jpreprocess_njd::{
+    pronunciation::{SymbolReading, SymbolTable},
+    NJD,
+};
+pub use lindera_core::mode::{Mode, Penalty};
 
-use jpreprocess_core::{error::JPreprocessErrorKind, *};
-use jpreprocess_dictionary::{metadata::detect_dictionary, WordDictionaryConfig};
+use jpreprocess_core::{error::JPreprocessErrorKind, word_entry::WordEntry, *};
+use jpreprocess_dictionary::overrides::WordOverrides;
 use lindera_core::dictionary::{Dictionary, UserDictionary};
 use lindera_dictionary::{load_user_dictionary, UserDictionaryConfig};
-use lindera_tokenizer::tokenizer::Tokenizer;
+use tokenizer::{LinderaTokenizer, Tokenizer};
 
 pub struct JPreprocessConfig {
     pub dictionary: SystemDictionaryConfig,
     pub user_dictionary: Option<UserDictionaryConfig>,
+    /// Options of the tokenizer. `Default::default()` keeps the default behavior.
+    pub tokenizer: TokenizerOptions,
+}
+
+/// Options of [`LinderaTokenizer`] in [`JPreprocessConfig`].
+///
+/// Set the fields to change and fill the rest with `..Default::default()`.
+#[derive(Clone)]
+pub struct TokenizerOptions {
+    /// Tokenization mode of lindera. [`Mode::Normal`] by default.
+    ///
+    /// [`Mode::Decompose`] penalizes long words, so that long compounds are split into
+    /// the words in the dictionary rather than read as a single (unknown) word.
+    pub mode: Mode,
+    /// Normalization applied to the text before tokenization.
+    pub normalizer: TextNormalizer,
 }
 
-pub struct JPreprocess {
-    tokenizer: Tokenizer,
-    dictionary_config: WordDictionaryConfig,
+impl Default for TokenizerOptions {
+    fn default() -> Self {
+        Self {
+            mode: Mode::Normal,
+            normalizer: TextNormalizer::default(),
+        }
+    }
+}
+
+pub struct JPreprocess<T = LinderaTokenizer> {
+    tokenizer: T,
+    symbol_table: SymbolTable,
 }
 
 impl JPreprocess {
@@ -79,6 +123,7 @@ impl JPreprocess {
     ///  let config = JPreprocessConfig {
     ///      dictionary: SystemDictionaryConfig::File(path),
     ///      user_dictionary: None,
+    ///      tokenizer: Default::default(),
     ///  };
     /// let jpreprocess = JPreprocess::from_config(config)?;
     /// #
@@ -97,6 +142,7 @@ impl JPreprocess {
     ///  let config = JPreprocessConfig {
     ///      dictionary: SystemDictionaryConfig::Bundled(JPreprocessDictionaryKind::NaistJdic),
     ///      user_dictionary: None,
+    ///      tokenizer: Default::default(),
     ///  };
     /// let jpreprocess = JPreprocess::from_config(config)?;
     /// #
@@ -105,6 +151,29 @@ impl JPreprocess {
     /// # #[cfg(not(feature = "naist-jdic"))]
     /// # fn main() {}
     /// ```
+    ///
+    /// ## Example 3: Configure the tokenizer
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use std::path::PathBuf;
+    /// use jpreprocess::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// #     let path = PathBuf::from("tests/min-dict");
+    ///  let config = JPreprocessConfig {
+    ///      dictionary: SystemDictionaryConfig::File(path),
+    ///      user_dictionary: None,
+    ///      tokenizer: TokenizerOptions {
+    ///          mode: Mode::Decompose(Penalty::default()),
+    ///          ..Default::default()
+    ///      },
+    ///  };
+    /// let jpreprocess = JPreprocess::from_config(config)?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
     pub fn from_config(config: JPreprocessConfig) -> JPreprocessResult<Self> {
         let dictionary = config.dictionary.load()?;
 
@@ -116,32 +185,111 @@ impl JPreprocess {
             None => None,
         };
 
-        Ok(Self::new(dictionary, user_dictionary))
+        let mut tokenizer =
+            LinderaTokenizer::new(dictionary, user_dictionary, config.tokenizer.mode);
+        tokenizer.set_normalizer(config.tokenizer.normalizer);
+
+        Ok(Self::with_tokenizer(tokenizer))
     }
 
     /// Creates JPreprocess from dictionaries.
     ///
+    /// The tokenizer runs in [`Mode::Normal`].
+    ///
     /// Note: `new` before v0.2.0 has moved to `from_config`
     pub fn new(dictionary: Dictionary, user_dictionary: Option<UserDictionary>) -> Self {
-        let dictionary_config = WordDictionaryConfig {
-            system: detect_dictionary(&dictionary.words_idx_data, &dictionary.words_data),
-            user: user_dictionary.as_ref().map(|user_dictionary| {
-                detect_dictionary(&user_dictionary.words_idx_data, &user_dictionary.words_data)
-            }),
-        };
-
-        let tokenizer = Tokenizer::new(
+        Self::with_tokenizer(LinderaTokenizer::new(
             dictionary,
             user_dictionary,
-            lindera_core::mode::Mode::Normal,
-        );
+            Mode::Normal,
+        ))
+    }
+
+    /// Sets the table to correct the pronunciation or accent of dictionary words
+    /// without changing the tokenization.
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use std::path::PathBuf;
+    /// use jpreprocess::*;
+    /// use jpreprocess_core::pos::POS;
+    /// use jpreprocess_dictionary::overrides::{WordOverride, WordOverrides};
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// #     let path = PathBuf::from("tests/min-dict");
+    /// #  let config = JPreprocessConfig {
+    /// #      dictionary: SystemDictionaryConfig::File(path),
+    /// #      user_dictionary: None,
+    /// #      tokenizer: Default::default(),
+    /// #  };
+    /// let mut jpreprocess = JPreprocess::from_config(config)?;
+    ///
+    /// let mut overrides = WordOverrides::default();
+    /// overrides.insert_surface(
+    ///     "音声",
+    ///     POS::from_strs("名詞", "一般", "*", "*")?,
+    ///     WordOverride {
+    ///         acc: Some(0),
+    ///         ..Default::default()
+    ///     },
+    /// );
+    /// jpreprocess.set_word_overrides(overrides);
+    ///
+    /// let njd = jpreprocess.text_to_njd("音声")?;
+    /// assert_eq!(njd.nodes[0].get_acc(), 0);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn set_word_overrides(&mut self, overrides: WordOverrides) {
+        self.tokenizer.set_word_overrides(overrides);
+    }
+}
 
+impl<T: Tokenizer> JPreprocess<T> {
+    /// Creates JPreprocess with a tokenizer other than lindera.
+    ///
+    /// See [`Tokenizer`] for details.
+    pub fn with_tokenizer(tokenizer: T) -> Self {
         Self {
             tokenizer,
-            dictionary_config,
+            symbol_table: SymbolTable::default(),
         }
     }
 
+    /// Sets the readings of the symbols which have no pronunciation in the dictionary,
+    /// e.g. `※` or `−`.
+    ///
+    /// The table is used by [`run_frontend`] and [`extract_fullcontext`].
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use std::path::PathBuf;
+    /// use jpreprocess::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// #     let path = PathBuf::from("tests/min-dict");
+    /// #  let config = JPreprocessConfig {
+    /// #      dictionary: SystemDictionaryConfig::File(path),
+    /// #      user_dictionary: None,
+    /// #      tokenizer: Default::default(),
+    /// #  };
+    /// let mut jpreprocess = JPreprocess::from_config(config)?;
+    ///
+    /// let mut symbols = SymbolTable::default();
+    /// symbols.insert("※", SymbolReading::new("チューイ", 1)?);
+    /// jpreprocess.set_symbol_table(symbols);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`run_frontend`]: #method.run_frontend
+    /// [`extract_fullcontext`]: #method.extract_fullcontext
+    pub fn set_symbol_table(&mut self, symbol_table: SymbolTable) {
+        self.symbol_table = symbol_table;
+    }
+
     /// Tokenize input text and return NJD.
     ///
     /// Useful for customizing text processing.
@@ -157,6 +305,7 @@ impl JPreprocess {
     /// #  let config = JPreprocessConfig {
     /// #      dictionary: SystemDictionaryConfig::File(path),
     /// #      user_dictionary: None,
+    /// #      tokenizer: Default::default(),
     /// #  };
     /// let jpreprocess = JPreprocess::from_config(config)?;
     ///
@@ -181,13 +330,331 @@ impl JPreprocess {
     /// # }
     /// ```
     pub fn text_to_njd(&self, text: &str) -> JPreprocessResult<NJD> {
-        let normalized_input_text = normalize_text_for_naist_jdic(text);
-        let tokens = self
+        let entries = self.tokenizer.tokenize(text)?;
+
+        Ok(NJD::from_entries(entries))
+    }
+
+    /// Normalizes the text in the same way as [`text_to_njd`], and returns the map of the offsets
+    /// from the normalized text to the input text.
+    ///
+    /// The strings of the nodes returned by [`text_to_njd`] (before [`NJD::preprocess`])
+    /// are the parts of the normalized text in order, so they can be mapped back to the input text.
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use std::path::PathBuf;
+    /// use jpreprocess::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// #     let path = PathBuf::from("tests/min-dict");
+    /// #  let config = JPreprocessConfig {
+    /// #      dictionary: SystemDictionaryConfig::File(path),
+    /// #      user_dictionary: None,
+    /// #      tokenizer: Default::default(),
+    /// #  };
+    /// let jpreprocess = JPreprocess::from_config(config)?;
+    ///
+    /// let text = "ｵﾝｾｲ合成";
+    /// let njd = jpreprocess.text_to_njd(text)?;
+    /// let (normalized, offsets) = jpreprocess.normalize_with_offsets(text);
+    ///
+    /// let mut start = 0;
+    /// let mut sources = vec![];
+    /// for node in &njd.nodes {
+    ///     let end = start + node.get_string().len();
+    ///     assert_eq!(&normalized[start..end], node.get_string());
+    ///     sources.push(&text[offsets.source_range(start..end)]);
+    ///     start = end;
+    /// }
+    /// assert_eq!(sources.concat(), text);
+    /// assert_eq!(sources.last(), Some(&"合成"));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`text_to_njd`]: #method.text_to_njd
+    pub fn normalize_with_offsets(&self, text: &str) -> (String, OffsetMap) {
+        self.tokenizer.normalize_with_offsets(text)
+    }
+
+    /// Tokenize input text into up to `n` candidates of NJD, the best first, with their costs.
+    ///
+    /// Choose one of them (e.g. the one with the desired reading), and preprocess it.
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use std::path::PathBuf;
+    /// use jpreprocess::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// #     let path = PathBuf::from("tests/min-dict");
+    /// #  let config = JPreprocessConfig {
+    /// #      dictionary: SystemDictionaryConfig::File(path),
+    /// #      user_dictionary: None,
+    /// #      tokenizer: Default::default(),
+    /// #  };
+    /// let jpreprocess = JPreprocess::from_config(config)?;
+    ///
+    /// let candidates = jpreprocess.text_to_njd_nbest("音声合成", 3)?;
+    /// assert!(candidates.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    ///
+    /// let (_cost, mut njd) = candidates.into_iter().next().unwrap();
+    /// njd.preprocess();
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn text_to_njd_nbest(&self, text: &str, n: usize) -> JPreprocessResult<Vec<(i32, NJD)>> {
+        Ok(self
             .tokenizer
-            .tokenize(normalized_input_text.as_str())
-            .map_err(|err| JPreprocessErrorKind::LinderaError.with_error(err))?;
+            .tokenize_nbest(text, n)?
+            .into_iter()
+            .map(|segmentation| (segmentation.cost, NJD::from_entries(segmentation.words)))
+            .collect())
+    }
 
-        NJD::from_tokens(&tokens, self.dictionary_config)
+    /// Tokenize a text with ruby (furigana) markup, and return NJD with the annotated readings.
+    ///
+    /// See [`parse_ruby`] for the markup.
+    /// The whole text is tokenized at once, and the words of each annotated part
+    /// are joined into a single node with the reading.
+    /// A word crossing the boundary of the annotated part is split at the boundary.
+    /// The part of speech is taken from the last word of the annotated part.
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use std::path::PathBuf;
+    /// use jpreprocess::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// #     let path = PathBuf::from("tests/min-dict");
+    /// #  let config = JPreprocessConfig {
+    /// #      dictionary: SystemDictionaryConfig::File(path),
+    /// #      user_dictionary: None,
+    /// #      tokenizer: Default::default(),
+    /// #  };
+    /// let jpreprocess = JPreprocess::from_config(config)?;
+    ///
+    /// let mut njd = jpreprocess.ruby_text_to_njd("｜音声《おんじょう》合成")?;
+    /// assert_eq!(njd.nodes[0].get_read(), Some("オンジョウ"));
+    /// njd.preprocess();
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn ruby_text_to_njd(&self, markup: &str) -> JPreprocessResult<NJD> {
+        let segments = parse_ruby(markup);
+        let pieces: Vec<&str> = segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect();
+
+        let mut nodes = vec![];
+        for (segment, njd) in segments.iter().zip(self.tokenize_pieces(&pieces)?) {
+            match &segment.reading {
+                Some(reading) => nodes.push(ruby::set_ruby_reading(njd, reading)?),
+                None => nodes.extend(njd.nodes),
+            }
+        }
+        Ok(NJD { nodes })
+    }
+
+    /// Tokenize a document in a subset of SSML, and return NJD.
+    ///
+    /// See [`parse_ssml`] for the supported elements.
+    /// - `<break>` and the boundaries of `<prosody>` become pauses, i.e. the boundaries of breath groups.
+    /// - `<phoneme>` works like the ruby of [`ruby_text_to_njd`](#method.ruby_text_to_njd).
+    /// - `<say-as>` sets whether the digits are read as a number or one by one,
+    ///   which is used in the digit stage of [`NJD::preprocess`].
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use std::path::PathBuf;
+    /// use jpreprocess::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// #     let path = PathBuf::from("tests/min-dict");
+    /// #  let config = JPreprocessConfig {
+    /// #      dictionary: SystemDictionaryConfig::File(path),
+    /// #      user_dictionary: None,
+    /// #      tokenizer: Default::default(),
+    /// #  };
+    /// let jpreprocess = JPreprocess::from_config(config)?;
+    ///
+    /// let mut njd = jpreprocess.ssml_to_njd(
+    ///     r#"<speak><phoneme alphabet="x-jpreprocess-kana" ph="おんじょう">音声</phoneme><break/>合成</speak>"#,
+    /// )?;
+    /// assert_eq!(njd.nodes[0].get_read(), Some("オンジョウ"));
+    /// assert!(njd.nodes[1].get_pron().is_touten());
+    /// njd.preprocess();
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn ssml_to_njd(&self, ssml: &str) -> JPreprocessResult<NJD> {
+        let segments = parse_ssml(ssml)?;
+        let pieces: Vec<&str> = segments
+            .iter()
+            .map(|segment| match segment {
+                SsmlSegment::Text(text) | SsmlSegment::Phoneme { text, .. } => text.as_str(),
+                // converted and tokenized separately
+                SsmlSegment::SayAs { .. } | SsmlSegment::Break { .. } => "",
+            })
+            .collect();
+        let njds = self.tokenize_pieces(&pieces)?;
+
+        let mut nodes: Vec<jpreprocess_njd::NJDNode> = vec![];
+        for (segment, njd) in segments.into_iter().zip(njds) {
+            match segment {
+                SsmlSegment::Text(_) => nodes.extend(njd.nodes),
+                SsmlSegment::Phoneme { kana, .. } => {
+                    nodes.push(ruby::set_ruby_reading(njd, &kana)?)
+                }
+                SsmlSegment::SayAs { text, interpret_as } => {
+                    nodes.extend(ssml::say_as_to_nodes(&text, &interpret_as, |text| {
+                        self.text_to_njd(text)
+                    })?)
+                }
+                SsmlSegment::Break { .. } => ssml::push_pause(&mut nodes),
+            }
+        }
+        Ok(NJD { nodes })
+    }
+
+    /// Tokenize a text with inline annotations, and return NJD.
+    ///
+    /// See [`parse_annotation`] for the syntax.
+    /// The annotated words and the words just after `|` start new accent phrases,
+    /// and the pronunciation and accent of the annotated words are kept in [`NJD::preprocess`]
+    /// unless the following words are combined into the accent phrase.
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use std::path::PathBuf;
+    /// use jpreprocess::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// #     let path = PathBuf::from("tests/min-dict");
+    /// #  let config = JPreprocessConfig {
+    /// #      dictionary: SystemDictionaryConfig::File(path),
+    /// #      user_dictionary: None,
+    /// #      tokenizer: Default::default(),
+    /// #  };
+    /// let jpreprocess = JPreprocess::from_config(config)?;
+    ///
+    /// let mut njd = jpreprocess.annotated_text_to_njd("{音声/オ'ンセー}|合成")?;
+    /// njd.preprocess();
+    /// assert_eq!(njd.nodes[0].get_acc(), 1);
+    /// assert_eq!(njd.nodes[1].get_chain_flag(), Some(false));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn annotated_text_to_njd(&self, text: &str) -> JPreprocessResult<NJD> {
+        let segments = parse_annotation(text)?;
+        let pieces: Vec<&str> = segments
+            .iter()
+            .map(|segment| match segment {
+                AnnotatedSegment::Text(text) | AnnotatedSegment::Word { text, .. } => text.as_str(),
+                AnnotatedSegment::AccentPhraseBoundary | AnnotatedSegment::Pause => "",
+            })
+            .collect();
+        let njds = self.tokenize_pieces(&pieces)?;
+
+        let mut nodes: Vec<jpreprocess_njd::NJDNode> = vec![];
+        let mut boundary = false;
+        for (segment, njd) in segments.into_iter().zip(njds) {
+            let start = nodes.len();
+            match segment {
+                AnnotatedSegment::Text(_) => nodes.extend(njd.nodes),
+                AnnotatedSegment::Word { reading, acc, .. } => {
+                    let mut node = ruby::set_ruby_reading(njd, &reading)?;
+                    node.set_acc(acc);
+                    node.set_chain_flag(false);
+                    nodes.push(node);
+                }
+                AnnotatedSegment::AccentPhraseBoundary => {
+                    boundary = true;
+                    continue;
+                }
+                AnnotatedSegment::Pause => ssml::push_pause(&mut nodes),
+            }
+            if let Some(node) = nodes.get_mut(start).filter(|_| boundary) {
+                node.set_chain_flag(false);
+                boundary = false;
+            }
+        }
+        Ok(NJD { nodes })
+    }
+
+    /// Tokenizes the text made of the pieces at once, and returns NJD of each piece.
+    ///
+    /// The words crossing the boundaries of the pieces are split at the boundaries,
+    /// and each part is tokenized again.
+    /// If the text is normalized differently from the pieces (e.g. by a replace rule
+    /// matching across the boundaries), the pieces are tokenized separately.
+    fn tokenize_pieces(&self, pieces: &[&str]) -> JPreprocessResult<Vec<NJD>> {
+        let normalized: String = pieces
+            .iter()
+            .map(|piece| self.tokenizer.normalize(piece))
+            .collect();
+        let text = pieces.concat();
+        let words = self.tokenizer.tokenize(&text)?;
+        if self.tokenizer.normalize(&text) != normalized
+            || words
+                .iter()
+                .map(|(surface, _)| surface.len())
+                .sum::<usize>()
+                != normalized.len()
+        {
+            return pieces.iter().map(|piece| self.text_to_njd(piece)).collect();
+        }
+
+        // the end of each piece in the normalized text
+        let ends: Vec<usize> = pieces
+            .iter()
+            .scan(0, |end, piece| {
+                *end += self.tokenizer.normalize(piece).len();
+                Some(*end)
+            })
+            .collect();
+
+        let mut entries: Vec<Vec<(String, WordEntry)>> = vec![vec![]; pieces.len()];
+        let mut piece = 0;
+        let mut start = 0;
+        for (surface, entry) in words {
+            let end = start + surface.len();
+            while ends[piece] <= start {
+                piece += 1;
+            }
+            if end <= ends[piece] {
+                entries[piece].push((surface, entry));
+            } else {
+                let mut part_start = start;
+                while part_start < end {
+                    let part_end = end.min(ends[piece]);
+                    let part = &normalized[part_start..part_end];
+                    entries[piece].extend(self.tokenizer.tokenize(part)?);
+                    part_start = part_end;
+                    if part_start == ends[piece] {
+                        piece += 1;
+                    }
+                }
+            }
+            start = end;
+        }
+
+        Ok(entries.into_iter().map(NJD::from_entries).collect())
+    }
+
+    /// Split a document into sentences with [`split_sentences`], and tokenize each of them.
+    pub fn text_to_njd_document(&self, text: &str) -> JPreprocessResult<Vec<NJD>> {
+        split_sentences(text)
+            .into_iter()
+            .map(|sentence| self.text_to_njd(sentence))
+            .collect()
     }
 
     /// Tokenize a text, preprocess, and return NJD converted to string.
@@ -199,7 +666,7 @@ impl JPreprocess {
     /// If you need these infomation, please raise a feature request as an issue.
     pub fn run_frontend(&self, text: &str) -> JPreprocessResult<Vec<String>> {
         let mut njd = Self::text_to_njd(self, text)?;
-        njd.preprocess();
+        njd.preprocess_with_symbols(&self.symbol_table);
         Ok(njd.into())
     }
 
@@ -219,7 +686,47 @@ impl JPreprocess {
     /// [`make_label`]: #method.make_label
     pub fn extract_fullcontext(&self, text: &str) -> JPreprocessResult<Vec<String>> {
         let mut njd = Self::text_to_njd(self, text)?;
-        njd.preprocess();
+        njd.preprocess_with_symbols(&self.symbol_table);
         Ok(jpreprocess_jpcommon::njdnodes_to_features(&njd.nodes))
     }
+
+    /// Generate jpcommon features for each sentence of a document.
+    ///
+    /// The utterance-level features (e.g. the number of moras) are limited,
+    /// so a long paragraph should be split into sentences rather than passed to
+    /// [`extract_fullcontext`] at once.
+    /// See [`split_sentences`] for how the document is split.
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use std::path::PathBuf;
+    /// use jpreprocess::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// #     let path = PathBuf::from("tests/min-dict");
+    /// #  let config = JPreprocessConfig {
+    /// #      dictionary: SystemDictionaryConfig::File(path),
+    /// #      user_dictionary: None,
+    /// #      tokenizer: Default::default(),
+    /// #  };
+    /// let jpreprocess = JPreprocess::from_config(config)?;
+    ///
+    /// let labels = jpreprocess.extract_fullcontext_document("音声合成。\nエンジン。")?;
+    /// assert_eq!(labels.len(), 2);
+    /// assert_eq!(labels[1], jpreprocess.extract_fullcontext("エンジン。")?);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`extract_fullcontext`]: #method.extract_fullcontext
+    pub fn extract_fullcontext_document(&self, text: &str) -> JPreprocessResult<Vec<Vec<String>>> {
+        self.text_to_njd_document(text)?
+            .into_iter()
+            .map(|mut njd| {
+                njd.preprocess_with_symbols(&self.symbol_table);
+                Ok(jpreprocess_jpcommon::njdnodes_to_features(&njd.nodes))
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,200 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use jpreprocess_core::{error::JPreprocessErrorKind, JPreprocessResult};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::offsets::Edit;
+
+const BUNDLED_NAMES: &str = include_str!("emoji_ja.tsv");
+
+/// An emoji, possibly with a skin tone modifier and ZWJ sequence, a flag, or a keycap.
+///
+/// The pictographs in text presentation (e.g. `©` without U+FE0F) are not regarded as emoji.
+static EMOJI_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"\p{Regional_Indicator}{2}",
+        r"|[0-9#*]\x{FE0F}?\x{20E3}",
+        r"|(?:\p{Emoji_Presentation}\x{FE0F}?|\p{Extended_Pictographic}\x{FE0F})\p{Emoji_Modifier}?",
+        r"(?:\x{200D}\p{Extended_Pictographic}\x{FE0F}?\p{Emoji_Modifier}?)*",
+    ))
+    .expect("Failed to compile emoji regex")
+});
+
+/// What to do with emoji.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmojiHandling {
+    /// Replaces the emoji with its name.
+    /// The emoji without a name are replaced with a pause, the same as [`EmojiHandling::Pause`].
+    Read,
+    Drop,
+    /// Replaces the emoji with `、`, which is read as a short pause.
+    Pause,
+}
+
+/// Reads, drops or replaces with pauses the emoji.
+///
+/// The bundled names are the Japanese text-to-speech names of CLDR,
+/// generated by `scripts/emoji_ja.py` (see the header of `emoji_ja.tsv`).
+/// Other names can be added with [`Self::load_names`].
+///
+/// ```rust
+/// use jpreprocess::{EmojiHandling, EmojiReader};
+///
+/// let mut reader = EmojiReader::new(EmojiHandling::Read);
+/// reader.load_names("🙏\t合掌")?;
+/// assert_eq!(reader.convert("ありがとう🙏🏻"), "ありがとう合掌");
+///
+/// let reader = EmojiReader::new(EmojiHandling::Pause);
+/// assert_eq!(reader.convert("晴れ☀️明日は雨☔"), "晴れ、明日は雨、");
+/// # Ok::<(), jpreprocess_core::JPreprocessError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct EmojiReader {
+    pub handling: EmojiHandling,
+    names: HashMap<String, String>,
+}
+
+impl EmojiReader {
+    /// Creates the reader with the bundled names.
+    pub fn new(handling: EmojiHandling) -> Self {
+        let mut reader = Self {
+            handling,
+            names: HashMap::new(),
+        };
+        reader
+            .load_names(BUNDLED_NAMES)
+            .expect("Failed to load bundled emoji names");
+        reader
+    }
+
+    /// Adds or overwrites the names, given as lines of `emoji<TAB>name`.
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn load_names(&mut self, names: &str) -> JPreprocessResult<()> {
+        for (i, line) in names.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (emoji, name) = line.split_once('\t').ok_or_else(|| {
+                JPreprocessErrorKind::NormalizeRuleParseError.with_error(anyhow::anyhow!(
+                    "line {}: emoji and name must be separated by a tab",
+                    i + 1
+                ))
+            })?;
+            self.insert(emoji, name);
+        }
+        Ok(())
+    }
+
+    /// Reads the names from a UTF-8 file. See [`Self::load_names`] for the format.
+    pub fn load_names_from_file(&mut self, path: &Path) -> JPreprocessResult<()> {
+        let names = fs::read_to_string(path)
+            .map_err(|err| JPreprocessErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        self.load_names(&names)
+    }
+
+    pub fn insert(&mut self, emoji: &str, name: &str) {
+        self.names
+            .insert(strip_variation_selectors(emoji), name.to_string());
+    }
+
+    /// Looks up the name of the emoji.
+    ///
+    /// If the emoji itself does not have a name, that without skin tone modifiers,
+    /// or the first emoji of the ZWJ sequence is used.
+    pub fn name(&self, emoji: &str) -> Option<&str> {
+        let emoji = strip_variation_selectors(emoji);
+        let without_modifiers: String = emoji
+            .chars()
+            .filter(|c| !('\u{1F3FB}'..='\u{1F3FF}').contains(c))
+            .collect();
+        let first = without_modifiers.split('\u{200D}').next().unwrap_or("");
+        for key in [emoji.as_str(), without_modifiers.as_str(), first] {
+            if let Some(name) = self.names.get(key) {
+                return Some(name);
+            }
+        }
+        None
+    }
+
+    pub fn convert(&self, text: &str) -> String {
+        EMOJI_REGEX
+            .replace_all(text, |captures: &regex::Captures| {
+                self.replacement(&captures[0])
+            })
+            .into_owned()
+    }
+
+    /// Same as [`Self::convert`], but also returns the replaced parts.
+    pub(crate) fn convert_with_offsets(&self, text: &str) -> (String, Vec<Edit>) {
+        let mut result = String::with_capacity(text.len());
+        let mut edits = vec![];
+        let mut last = 0;
+        for m in EMOJI_REGEX.find_iter(text) {
+            result.push_str(&text[last..m.start()]);
+            let start = result.len();
+            result.push_str(self.replacement(m.as_str()));
+            edits.push(Edit {
+                source: m.range(),
+                target: start..result.len(),
+            });
+            last = m.end();
+        }
+        result.push_str(&text[last..]);
+        (result, edits)
+    }
+
+    fn replacement(&self, emoji: &str) -> &str {
+        match self.handling {
+            EmojiHandling::Read => self.name(emoji).unwrap_or("、"),
+            EmojiHandling::Drop => "",
+            EmojiHandling::Pause => "、",
+        }
+    }
+}
+
+fn strip_variation_selectors(emoji: &str) -> String {
+    emoji.replace(['\u{FE0E}', '\u{FE0F}'], "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EmojiHandling, EmojiReader};
+
+    #[test]
+    fn read() {
+        let mut reader = EmojiReader::new(EmojiHandling::Read);
+        reader
+            .load_names("😂\tうれし泣き\n🇯🇵\t日本の国旗\n❤️\t赤いハート")
+            .unwrap();
+        assert_eq!(reader.convert("最高😂😂"), "最高うれし泣きうれし泣き");
+        assert_eq!(reader.convert("🇯🇵代表"), "日本の国旗代表");
+        // ZWJ sequence, falls back to the first emoji
+        assert_eq!(reader.convert("❤️‍🔥"), "赤いハート");
+        // no name
+        assert_eq!(reader.convert("a🦩b"), "a、b");
+
+        reader.load_names("# flamingo\n🦩\tフラミンゴ").unwrap();
+        assert_eq!(reader.convert("a🦩b"), "aフラミンゴb");
+        assert!(reader.load_names("🦩 フラミンゴ").is_err());
+    }
+
+    #[test]
+    fn drop_and_pause() {
+        let text = "了解👍です1️⃣";
+        assert_eq!(
+            EmojiReader::new(EmojiHandling::Drop).convert(text),
+            "了解です"
+        );
+        assert_eq!(
+            EmojiReader::new(EmojiHandling::Pause).convert(text),
+            "了解、です、"
+        );
+        // not emoji
+        assert_eq!(
+            EmojiReader::new(EmojiHandling::Drop).convert("1#*©"),
+            "1#*©"
+        );
+    }
+}
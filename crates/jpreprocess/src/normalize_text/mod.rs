@@ -0,0 +1,348 @@
+mod emoji;
+mod offsets;
+mod rules;
+mod web_text;
+
+pub use emoji::{EmojiHandling, EmojiReader};
+pub use offsets::OffsetMap;
+pub use rules::ReplaceRules;
+pub use web_text::*;
+
+use std::{collections::HashMap, sync::Arc};
+
+use once_cell::sync::Lazy;
+
+use offsets::{convert_chunks, diff, Edit};
+
+/// A step of [`TextNormalizer`].
+#[derive(Clone)]
+pub enum NormalizeStep {
+    /// Converts half-width katakana to full-width, and combines the voiced sound marks
+    /// (e.g. `ﾞ`, `゛`) with the preceding kana. The marks left alone are removed.
+    KanaCombining,
+    /// Replaces each character in the table, e.g. [`NormalizeStep::naist_jdic_symbols`].
+    SymbolMap(HashMap<char, String>),
+    /// Converts ASCII characters, space and `¥` to full-width.
+    WidthFolding,
+    Replace(ReplaceRules),
+    /// Converts URLs, email addresses, hashtags and mentions into readable text.
+    /// This should be the first step, as the other steps change ASCII characters.
+    WebText(WebTextReader),
+    /// Reads, drops or replaces with pauses the emoji.
+    /// This should precede [`NormalizeStep::WidthFolding`], which breaks keycap emoji.
+    Emoji(EmojiReader),
+    /// An arbitrary conversion.
+    ///
+    /// For [`TextNormalizer::normalize_with_offsets`], the part between the longest common prefix
+    /// and suffix of the input and the output is regarded as replaced.
+    Custom(Arc<dyn Fn(&str) -> String + Send + Sync>),
+}
+
+impl NormalizeStep {
+    /// The symbols converted before [`NormalizeStep::WidthFolding`] in naist-jdic normalization.
+    pub fn naist_jdic_symbols() -> HashMap<char, String> {
+        [
+            ('\\', "\u{FFE5}"), // ￥ U+FFE5 FULLWIDTH YEN SIGN
+            ('-', "\u{2212}"),  // − U+2212 MINUS SIGN
+            ('~', "\u{301C}"),  // 〜 U+301C WAVE DASH
+            ('`', "\u{2018}"),  // ‘ U+2018 LEFT SINGLE QUOTATION MARK
+            ('"', "\u{201D}"),  // ” U+201D RIGHT DOUBLE QUOTATION MARK
+            ('\'', "\u{2019}"), // ’ U+2019 RIGHT SINGLE QUOTATION MARK
+        ]
+        .into_iter()
+        .map(|(from, to)| (from, to.to_string()))
+        .collect()
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        match self {
+            Self::KanaCombining => kana::vsmark2full(&kana::combine(&kana::half2full(text)))
+                .replace(['\u{309B}', '\u{309C}'], ""),
+            Self::SymbolMap(map) => {
+                let mut result = String::with_capacity(text.len());
+                for c in text.chars() {
+                    match map.get(&c) {
+                        Some(to) => result.push_str(to),
+                        None => result.push(c),
+                    }
+                }
+                result
+            }
+            Self::WidthFolding => kana::yen2wide(&kana::space2wide(&kana::ascii2wide(text))),
+            Self::Replace(rules) => rules.apply(text),
+            Self::WebText(reader) => reader.convert(text).text,
+            Self::Emoji(reader) => reader.convert(text),
+            Self::Custom(f) => f(text),
+        }
+    }
+
+    fn apply_with_offsets(&self, text: &str, offsets: &mut OffsetMap) -> String {
+        let (result, edits) = match self {
+            // The voiced sound marks are combined with the preceding character only.
+            Self::KanaCombining => {
+                convert_chunks(text, split_before_base(text), |chunk| self.apply(chunk))
+            }
+            // These convert each character separately.
+            Self::SymbolMap(_) | Self::WidthFolding => {
+                let chars = text.char_indices().map(|(i, c)| &text[i..i + c.len_utf8()]);
+                convert_chunks(text, chars, |chunk| self.apply(chunk))
+            }
+            Self::Replace(rules) => return rules.apply_with_offsets(text, offsets),
+            Self::WebText(reader) => {
+                let conversion = reader.convert(text);
+                let edits = conversion
+                    .spans
+                    .into_iter()
+                    .map(|span| Edit {
+                        source: span.source,
+                        target: span.target,
+                    })
+                    .collect();
+                (conversion.text, edits)
+            }
+            Self::Emoji(reader) => reader.convert_with_offsets(text),
+            Self::Custom(f) => {
+                let result = f(text);
+                let edits = diff(text, &result);
+                (result, edits)
+            }
+        };
+        offsets.push_step(edits);
+        result
+    }
+}
+
+/// Splits the text before each character other than the voiced sound marks.
+fn split_before_base(text: &str) -> impl Iterator<Item = &str> {
+    const MARKS: [char; 6] = [
+        '\u{FF9E}', '\u{FF9F}', '\u{309B}', '\u{309C}', '\u{3099}', '\u{309A}',
+    ];
+
+    let mut starts = text
+        .char_indices()
+        .filter(|(i, c)| *i == 0 || !MARKS.contains(c))
+        .map(|(i, _)| i)
+        .chain([text.len()])
+        .peekable();
+    std::iter::from_fn(move || {
+        let start = starts.next()?;
+        let end = *starts.peek()?;
+        Some(&text[start..end])
+    })
+}
+
+/// Normalizes the text before tokenization, by applying the steps in order.
+///
+/// The default is the normalization for naist-jdic ([`TextNormalizer::naist_jdic`]).
+/// To use another one in [`JPreprocess`](crate::JPreprocess), set it to
+/// [`TokenizerOptions::normalizer`](crate::TokenizerOptions::normalizer) in
+/// [`JPreprocessConfig`](crate::JPreprocessConfig).
+///
+/// ```rust
+/// # use std::str::FromStr;
+/// use jpreprocess::{NormalizeStep, ReplaceRules, TextNormalizer};
+///
+/// let normalizer = TextNormalizer::naist_jdic()
+///     .with_step(NormalizeStep::Replace(ReplaceRules::from_str("ｗ+$\t笑い")?));
+/// assert_eq!(normalizer.normalize("面白いww"), "面白い笑い");
+///
+/// // For the input which is already normalized
+/// assert_eq!(TextNormalizer::new(vec![]).normalize("面白いww"), "面白いww");
+/// # Ok::<(), jpreprocess_core::JPreprocessError>(())
+/// ```
+#[derive(Clone)]
+pub struct TextNormalizer {
+    steps: Vec<NormalizeStep>,
+}
+
+impl TextNormalizer {
+    pub fn new(steps: Vec<NormalizeStep>) -> Self {
+        Self { steps }
+    }
+
+    /// Same as [`normalize_text_for_naist_jdic`].
+    pub fn naist_jdic() -> Self {
+        Self::new(vec![
+            NormalizeStep::KanaCombining,
+            NormalizeStep::SymbolMap(NormalizeStep::naist_jdic_symbols()),
+            NormalizeStep::WidthFolding,
+        ])
+    }
+
+    /// Appends a step.
+    pub fn with_step(mut self, step: NormalizeStep) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Inserts a step before the others, e.g. [`NormalizeStep::Emoji`].
+    pub fn with_first_step(mut self, step: NormalizeStep) -> Self {
+        self.steps.insert(0, step);
+        self
+    }
+
+    pub fn steps(&self) -> &[NormalizeStep] {
+        &self.steps
+    }
+
+    pub fn normalize(&self, text: &str) -> String {
+        self.steps
+            .iter()
+            .fold(text.to_string(), |text, step| step.apply(&text))
+    }
+
+    /// Same as [`Self::normalize`], but also returns the map of the offsets
+    /// from the normalized text to the input text.
+    ///
+    /// ```rust
+    /// use jpreprocess::{NormalizeStep, TextNormalizer, WebTextReader, WebTextReading};
+    ///
+    /// let normalizer = TextNormalizer::naist_jdic().with_first_step(NormalizeStep::WebText(
+    ///     WebTextReader {
+    ///         url: WebTextReading::Replace("リンク".to_string()),
+    ///         ..Default::default()
+    ///     },
+    /// ));
+    /// let input = "見てhttps://example.com/ｶﾞ";
+    /// let (normalized, offsets) = normalizer.normalize_with_offsets(input);
+    /// assert_eq!(normalized, "見てリンクガ");
+    ///
+    /// let url = normalized.find("リンク").unwrap();
+    /// assert_eq!(&input[offsets.source_range(url..url + "リンク".len())], "https://example.com/");
+    /// let ga = normalized.find('ガ').unwrap();
+    /// assert_eq!(&input[offsets.source_range(ga..normalized.len())], "ｶﾞ");
+    /// ```
+    pub fn normalize_with_offsets(&self, text: &str) -> (String, OffsetMap) {
+        let mut offsets = OffsetMap::default();
+        let normalized = self.steps.iter().fold(text.to_string(), |text, step| {
+            step.apply_with_offsets(&text, &mut offsets)
+        });
+        (normalized, offsets)
+    }
+}
+
+impl Default for TextNormalizer {
+    fn default() -> Self {
+        Self::naist_jdic()
+    }
+}
+
+static NAIST_JDIC: Lazy<TextNormalizer> = Lazy::new(TextNormalizer::naist_jdic);
+
+/// Normalize input text
+pub fn normalize_text_for_naist_jdic(input_text: &str) -> String {
+    NAIST_JDIC.normalize(input_text)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use crate::normalize_text_for_naist_jdic;
+
+    use super::{NormalizeStep, ReplaceRules, TextNormalizer};
+
+    #[test]
+    fn ascii() {
+        assert_eq!(
+            normalize_text_for_naist_jdic(" !\"#$%&'()*+,-./"),
+            "　！”＃＄％＆’（）＊＋，−．／"
+        );
+        assert_eq!(
+            normalize_text_for_naist_jdic("0123456789"),
+            "０１２３４５６７８９"
+        );
+        assert_eq!(normalize_text_for_naist_jdic(":;<=>?@"), "：；＜＝＞？＠");
+        assert_eq!(
+            normalize_text_for_naist_jdic("ABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+            "ＡＢＣＤＥＦＧＨＩＪＫＬＭＮＯＰＱＲＳＴＵＶＷＸＹＺ"
+        );
+        assert_eq!(normalize_text_for_naist_jdic("[\\]^_`"), "［￥］＾＿‘");
+        assert_eq!(
+            normalize_text_for_naist_jdic("abcdefghijklmnopqrstuvwxyz"),
+            "ａｂｃｄｅｆｇｈｉｊｋｌｍｎｏｐｑｒｓｔｕｖｗｘｙｚ"
+        );
+        assert_eq!(normalize_text_for_naist_jdic("{|}~"), "｛｜｝〜");
+    }
+
+    #[test]
+    fn kana() {
+        assert_eq!(
+            normalize_text_for_naist_jdic("ｳﾞｶﾞｷﾞｸﾞｹﾞｺﾞｻﾞｼﾞｽﾞｾﾞｿﾞﾀﾞﾁﾞﾂﾞﾃﾞﾄﾞﾊﾞﾋﾞﾌﾞﾍﾞﾎﾞﾊﾟﾋﾟﾌﾟﾍﾟﾎﾟ"),
+            "ヴガギグゲゴザジズゼゾダヂヅデドバビブベボパピプペポ"
+        );
+        assert_eq!(normalize_text_for_naist_jdic("｡｢｣､･"), "。「」、・");
+        assert_eq!(
+            normalize_text_for_naist_jdic("ｦｧｨｩｪｫｬｭｮｯｰｱｲｳｴｵｶｷｸｹｺｻｼｽｾｿﾀﾁﾂﾃﾄﾅﾆﾇﾈﾉﾊﾋﾌﾍﾎﾏﾐﾑﾒﾓﾔﾕﾖﾗﾘﾙﾚﾛﾜﾝ"),
+            "ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン"
+        );
+    }
+
+    #[test]
+    fn diacritical() {
+        assert_eq!(normalize_text_for_naist_jdic("ﾞﾟ"), "");
+        assert_eq!(normalize_text_for_naist_jdic("あ゛"), "あ");
+        assert_eq!(normalize_text_for_naist_jdic("あ゜"), "あ");
+        assert_eq!(normalize_text_for_naist_jdic("は゛"), "ば");
+        assert_eq!(normalize_text_for_naist_jdic("は゜"), "ぱ");
+    }
+
+    #[test]
+    fn steps() {
+        let normalizer = TextNormalizer::new(vec![NormalizeStep::WidthFolding]);
+        assert_eq!(normalizer.normalize("ｶﾞ a-1"), "ｶﾞ　ａ－１");
+
+        let mut symbols = NormalizeStep::naist_jdic_symbols();
+        symbols.insert('〜', "ー".to_string());
+        let normalizer = TextNormalizer::new(vec![
+            NormalizeStep::Replace(ReplaceRules::from_str("^w+\tわら").unwrap()),
+            NormalizeStep::KanaCombining,
+            NormalizeStep::SymbolMap(symbols),
+            NormalizeStep::WidthFolding,
+            NormalizeStep::Custom(Arc::new(|text| text.replace("ｗ", "わら"))),
+        ]);
+        assert_eq!(normalizer.normalize("wwｽｺﾞ〜いw"), "わらスゴーいわら");
+    }
+
+    #[test]
+    fn offsets() {
+        let mut symbols = NormalizeStep::naist_jdic_symbols();
+        symbols.insert('〜', "ー".to_string());
+        let normalizer = TextNormalizer::new(vec![
+            NormalizeStep::Replace(ReplaceRules::from_str("^w+\tわら").unwrap()),
+            NormalizeStep::KanaCombining,
+            NormalizeStep::SymbolMap(symbols),
+            NormalizeStep::WidthFolding,
+            NormalizeStep::Custom(Arc::new(|text| text.replace("ｗ", "わら"))),
+        ]);
+        let input = "wwｽｺﾞ〜いw";
+        let (normalized, offsets) = normalizer.normalize_with_offsets(input);
+        assert_eq!(normalized, normalizer.normalize(input));
+
+        let source = |part: &str| {
+            let start = normalized.find(part).unwrap();
+            &input[offsets.source_range(start..start + part.len())]
+        };
+        assert_eq!(source("わらス"), "wwｽ");
+        assert_eq!(source("ゴ"), "ｺﾞ");
+        assert_eq!(source("ーい"), "〜い");
+        assert_eq!(
+            &input[offsets.source_range(normalized.len() - 6..normalized.len())],
+            "w"
+        );
+    }
+
+    #[test]
+    fn kana_offsets() {
+        for input in ["ｳﾞｶﾞｷﾞｸﾞｹﾞｺﾞﾊﾟﾋﾟ", "ﾞﾟあ゛あ゜は゛は゜", "か\u{3099}ｶﾞﾞA゛"]
+        {
+            let normalizer = TextNormalizer::new(vec![NormalizeStep::KanaCombining]);
+            assert_eq!(
+                normalizer.normalize_with_offsets(input).0,
+                normalizer.normalize(input),
+                "{}",
+                input
+            );
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use std::ops::Range;
+
+/// A part of the text replaced by a step of normalization.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Edit {
+    /// Byte range in the text given to the step.
+    pub source: Range<usize>,
+    /// Byte range in the text returned by the step.
+    pub target: Range<usize>,
+}
+
+/// Maps byte offsets in the normalized text back to the input text.
+///
+/// Returned by [`TextNormalizer::normalize_with_offsets`](crate::TextNormalizer::normalize_with_offsets),
+/// it keeps the replaced parts of every step, and maps the offsets through the steps in reverse order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OffsetMap {
+    steps: Vec<Vec<Edit>>,
+}
+
+impl OffsetMap {
+    pub(crate) fn push_step(&mut self, edits: Vec<Edit>) {
+        if !edits.is_empty() {
+            self.steps.push(edits);
+        }
+    }
+
+    /// Maps a byte offset in the normalized text to that in the input text.
+    ///
+    /// The offsets inside a replaced part are mapped to the start of the original part.
+    pub fn source_offset(&self, offset: usize) -> usize {
+        self.steps
+            .iter()
+            .rev()
+            .fold(offset, |offset, edits| map_edits(edits, offset, false))
+    }
+
+    /// Maps a byte range in the normalized text to that in the input text.
+    ///
+    /// The range is widened to cover the original parts which are partly in the range.
+    pub fn source_range(&self, range: Range<usize>) -> Range<usize> {
+        let start = self.source_offset(range.start);
+        let end = self
+            .steps
+            .iter()
+            .rev()
+            .fold(range.end, |offset, edits| map_edits(edits, offset, true));
+        start..end.max(start)
+    }
+}
+
+fn map_edits(edits: &[Edit], offset: usize, end: bool) -> usize {
+    map_offset(
+        edits.iter().map(|edit| (&edit.source, &edit.target)),
+        offset,
+        end,
+    )
+}
+
+/// Maps an offset through the replaced parts of a step, as pairs of the source and target ranges
+/// sorted by position.
+///
+/// If `end` is true, the offset is regarded as the (exclusive) end of a range.
+pub(crate) fn map_offset<'a, I>(edits: I, offset: usize, end: bool) -> usize
+where
+    I: IntoIterator<Item = (&'a Range<usize>, &'a Range<usize>)>,
+{
+    let mut shift: isize = 0;
+    for (source, target) in edits {
+        if offset < target.start || (end && offset == target.start) {
+            break;
+        }
+        if offset < target.end {
+            return if end { source.end } else { source.start };
+        }
+        shift = source.end as isize - target.end as isize;
+    }
+    (offset as isize + shift) as usize
+}
+
+/// Converts each chunk of the text with `f`, and records the chunks which have changed.
+pub(crate) fn convert_chunks<'a, C, F>(text: &'a str, chunks: C, f: F) -> (String, Vec<Edit>)
+where
+    C: IntoIterator<Item = &'a str>,
+    F: Fn(&str) -> String,
+{
+    let mut result = String::with_capacity(text.len());
+    let mut edits = vec![];
+    let mut start = 0;
+    for chunk in chunks {
+        let converted = f(chunk);
+        if converted != chunk {
+            edits.push(Edit {
+                source: start..start + chunk.len(),
+                target: result.len()..result.len() + converted.len(),
+            });
+        }
+        result.push_str(&converted);
+        start += chunk.len();
+    }
+    (result, edits)
+}
+
+/// Finds the part of `source` replaced to get `target`, by trimming the common prefix and suffix.
+pub(crate) fn diff(source: &str, target: &str) -> Vec<Edit> {
+    if source == target {
+        return vec![];
+    }
+    let prefix: usize = source
+        .chars()
+        .zip(target.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|(c, _)| c.len_utf8())
+        .sum();
+    let suffix: usize = source[prefix..]
+        .chars()
+        .rev()
+        .zip(target[prefix..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(c, _)| c.len_utf8())
+        .sum();
+    vec![Edit {
+        source: prefix..source.len() - suffix,
+        target: prefix..target.len() - suffix,
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, Edit, OffsetMap};
+
+    #[test]
+    fn composed() {
+        // "ab" -> "aXYb" -> "aXY"
+        let mut map = OffsetMap::default();
+        map.push_step(diff("ab", "aXYb"));
+        map.push_step(diff("aXYb", "aXY"));
+
+        assert_eq!(map.source_offset(0), 0);
+        assert_eq!(map.source_offset(1), 1);
+        assert_eq!(map.source_offset(2), 1);
+        assert_eq!(map.source_range(1..3), 1..1);
+        // the removed `b` is not in the normalized text
+        assert_eq!(map.source_range(0..3), 0..1);
+    }
+
+    #[test]
+    fn replaced() {
+        let mut map = OffsetMap::default();
+        map.push_step(vec![Edit {
+            source: 2..6,
+            target: 2..3,
+        }]);
+
+        assert_eq!(map.source_range(0..2), 0..2);
+        assert_eq!(map.source_range(2..3), 2..6);
+        assert_eq!(map.source_range(3..5), 6..8);
+    }
+}
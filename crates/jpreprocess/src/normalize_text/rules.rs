@@ -0,0 +1,132 @@
+use std::{fs, path::Path, str::FromStr};
+
+use jpreprocess_core::{error::JPreprocessErrorKind, JPreprocessError, JPreprocessResult};
+use regex::Regex;
+
+use super::{offsets::Edit, OffsetMap};
+
+/// Regex replacements applied in order.
+///
+/// In the text form, each line is a rule of `pattern<TAB>replacement`.
+/// The replacement may refer to the capture groups (e.g. `$1`).
+/// Empty lines and lines starting with `#` are ignored.
+///
+/// ```rust
+/// # use std::str::FromStr;
+/// use jpreprocess::ReplaceRules;
+///
+/// let rules = ReplaceRules::from_str("# laughter\nｗ+$\t笑い\n(\\d)〜(\\d)\t${1}から${2}")?;
+/// assert_eq!(rules.apply("面白いｗｗ"), "面白い笑い");
+/// assert_eq!(rules.apply("3〜5個"), "3から5個");
+/// # Ok::<(), jpreprocess_core::JPreprocessError>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ReplaceRules {
+    rules: Vec<(Regex, String)>,
+}
+
+impl ReplaceRules {
+    /// Reads the rules from a UTF-8 file.
+    pub fn from_file(path: &Path) -> JPreprocessResult<Self> {
+        let rules = fs::read_to_string(path)
+            .map_err(|err| JPreprocessErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        Self::from_str(&rules)
+    }
+
+    pub fn push(&mut self, pattern: &str, replacement: &str) -> JPreprocessResult<()> {
+        let regex = Regex::new(pattern)
+            .map_err(|err| JPreprocessErrorKind::NormalizeRuleParseError.with_error(err))?;
+        self.rules.push((regex, replacement.to_string()));
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        self.rules
+            .iter()
+            .fold(text.to_string(), |text, (regex, replacement)| {
+                regex.replace_all(&text, replacement.as_str()).into_owned()
+            })
+    }
+
+    /// Same as [`Self::apply`], but records the replaced parts of each rule.
+    pub(crate) fn apply_with_offsets(&self, text: &str, offsets: &mut OffsetMap) -> String {
+        self.rules
+            .iter()
+            .fold(text.to_string(), |text, (regex, replacement)| {
+                let mut result = String::with_capacity(text.len());
+                let mut edits = vec![];
+                let mut last = 0;
+                for captures in regex.captures_iter(&text) {
+                    let m = captures.get(0).unwrap();
+                    result.push_str(&text[last..m.start()]);
+                    let start = result.len();
+                    captures.expand(replacement, &mut result);
+                    edits.push(Edit {
+                        source: m.range(),
+                        target: start..result.len(),
+                    });
+                    last = m.end();
+                }
+                result.push_str(&text[last..]);
+                offsets.push_step(edits);
+                result
+            })
+    }
+}
+
+impl FromStr for ReplaceRules {
+    type Err = JPreprocessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = Self::default();
+        for (i, line) in s.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (pattern, replacement) = line.split_once('\t').ok_or_else(|| {
+                JPreprocessErrorKind::NormalizeRuleParseError.with_error(anyhow::anyhow!(
+                    "line {}: pattern and replacement must be separated by a tab",
+                    i + 1
+                ))
+            })?;
+            rules.push(pattern, replacement).map_err(|err| {
+                JPreprocessErrorKind::NormalizeRuleParseError.with_error(anyhow::anyhow!(
+                    "line {}: {}",
+                    i + 1,
+                    err
+                ))
+            })?;
+        }
+        Ok(rules)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use jpreprocess_core::error::JPreprocessErrorKind;
+
+    use super::ReplaceRules;
+
+    #[test]
+    fn parse_error() {
+        let err = ReplaceRules::from_str("ok\tOK\nno tab").unwrap_err();
+        assert_eq!(err.kind, JPreprocessErrorKind::NormalizeRuleParseError);
+        assert!(err.to_string().contains("line 2"));
+
+        let err = ReplaceRules::from_str("(\tunclosed").unwrap_err();
+        assert_eq!(err.kind, JPreprocessErrorKind::NormalizeRuleParseError);
+    }
+
+    #[test]
+    fn in_order() {
+        let rules = ReplaceRules::from_str("a\tb\nb\tc").unwrap();
+        assert_eq!(rules.apply("ab"), "cc");
+        assert!(ReplaceRules::default().is_empty());
+    }
+}
@@ -0,0 +1,376 @@
+use std::ops::Range;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::offsets::map_offset;
+
+static URL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(?:https?|ftp)://[A-Za-z0-9\-._~:/?#\[\]@!$&'()*+,;=%]+")
+        .expect("Failed to compile url regex")
+});
+static EMAIL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[A-Za-z0-9._%+\-]+@[A-Za-z0-9\-]+(?:\.[A-Za-z0-9\-]+)+")
+        .expect("Failed to compile email regex")
+});
+static MENTION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[@＠][A-Za-z0-9_]+").expect("Failed to compile mention regex"));
+static HASHTAG_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[#＃][\p{L}\p{N}_ー]+").expect("Failed to compile hashtag regex"));
+
+/// Kind of the text recognized by [`WebTextReader`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebTextKind {
+    Url,
+    Email,
+    Hashtag,
+    Mention,
+}
+
+/// How to read the recognized text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WebTextReading {
+    /// Leaves the text as is.
+    Keep,
+    /// Spells the letters and symbols out, e.g. `エイチティーティーピーエス コロン スラッシュ`.
+    ///
+    /// For hashtags and mentions, only the ASCII letters in the name are spelled.
+    Spell,
+    /// Replaces the whole text, e.g. `URL省略`.
+    Replace(String),
+}
+
+/// A span replaced by [`WebTextReader`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebTextSpan {
+    pub kind: WebTextKind,
+    /// Byte range in the input text.
+    pub source: Range<usize>,
+    /// Byte range in the converted text.
+    pub target: Range<usize>,
+}
+
+/// The result of [`WebTextReader::convert`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebTextConversion {
+    pub text: String,
+    pub spans: Vec<WebTextSpan>,
+}
+
+impl WebTextConversion {
+    /// Maps a byte offset in the converted text to that in the input text.
+    ///
+    /// The offsets inside a replaced span are mapped to the start of the original span.
+    pub fn source_offset(&self, offset: usize) -> usize {
+        map_offset(
+            self.spans.iter().map(|span| (&span.source, &span.target)),
+            offset,
+            false,
+        )
+    }
+}
+
+/// Recognizes URLs, email addresses, hashtags and @mentions, and converts them into
+/// the text which can be read in Japanese.
+///
+/// This is meant to be the first step of [`TextNormalizer`](crate::TextNormalizer),
+/// as [`NormalizeStep::WebText`](crate::NormalizeStep::WebText).
+/// The offsets are kept by [`TextNormalizer::normalize_with_offsets`](crate::TextNormalizer::normalize_with_offsets).
+///
+/// ```rust
+/// use jpreprocess::{WebTextReader, WebTextReading};
+///
+/// let reader = WebTextReader::default();
+/// assert_eq!(
+///     reader.convert("詳細はhttps://example.com/aを参照").text,
+///     "詳細はエイチティーティーピーエス コロン スラッシュ スラッシュ \
+///      イーエックスエーエムピーエルイー ドット コム スラッシュ エーを参照"
+/// );
+///
+/// let reader = WebTextReader {
+///     url: WebTextReading::Replace("URL省略".to_string()),
+///     ..Default::default()
+/// };
+/// let conversion = reader.convert("見てhttps://example.com/ね");
+/// assert_eq!(conversion.text, "見てURL省略ね");
+/// // `ね` in the converted text is at the same position as in the input text
+/// assert_eq!(conversion.source_offset(conversion.text.find('ね').unwrap()), 26);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebTextReader {
+    pub url: WebTextReading,
+    pub email: WebTextReading,
+    pub hashtag: WebTextReading,
+    pub mention: WebTextReading,
+}
+
+impl Default for WebTextReader {
+    fn default() -> Self {
+        Self {
+            url: WebTextReading::Spell,
+            email: WebTextReading::Spell,
+            hashtag: WebTextReading::Spell,
+            mention: WebTextReading::Spell,
+        }
+    }
+}
+
+impl WebTextReader {
+    fn reading(&self, kind: WebTextKind) -> &WebTextReading {
+        match kind {
+            WebTextKind::Url => &self.url,
+            WebTextKind::Email => &self.email,
+            WebTextKind::Hashtag => &self.hashtag,
+            WebTextKind::Mention => &self.mention,
+        }
+    }
+
+    /// Finds the URLs, email addresses, hashtags and mentions in the text, without overlaps.
+    pub fn find(&self, text: &str) -> Vec<(WebTextKind, Range<usize>)> {
+        let mut found: Vec<(WebTextKind, Range<usize>)> = vec![];
+        let mut push = |kind: WebTextKind, range: Range<usize>| {
+            if !found
+                .iter()
+                .any(|(_, r)| r.start < range.end && range.start < r.end)
+            {
+                found.push((kind, range));
+            }
+        };
+
+        for m in URL_REGEX.find_iter(text) {
+            // punctuations at the end are likely to be a part of the sentence
+            let url = m.as_str().trim_end_matches(['.', ',', '!', '?', ')', '\'']);
+            push(WebTextKind::Url, m.start()..m.start() + url.len());
+        }
+        for m in EMAIL_REGEX.find_iter(text) {
+            push(WebTextKind::Email, m.range());
+        }
+        for (kind, regex) in [
+            (WebTextKind::Mention, &MENTION_REGEX),
+            (WebTextKind::Hashtag, &HASHTAG_REGEX),
+        ] {
+            for m in regex.find_iter(text) {
+                // e.g. `C#` or `a@b` is not a hashtag or a mention
+                let preceded_by_word = text[..m.start()]
+                    .chars()
+                    .next_back()
+                    .map(|c| c.is_alphanumeric() || c == '_')
+                    .unwrap_or(false);
+                if !preceded_by_word {
+                    push(kind, m.range());
+                }
+            }
+        }
+
+        found.sort_by_key(|(_, range)| range.start);
+        found
+    }
+
+    pub fn convert(&self, text: &str) -> WebTextConversion {
+        let mut result = String::with_capacity(text.len());
+        let mut spans = vec![];
+        let mut last = 0;
+        for (kind, source) in self.find(text) {
+            let converted = match self.reading(kind) {
+                WebTextReading::Keep => continue,
+                WebTextReading::Spell => spell(kind, &text[source.clone()]),
+                WebTextReading::Replace(replacement) => replacement.clone(),
+            };
+            result.push_str(&text[last..source.start]);
+            let start = result.len();
+            result.push_str(&converted);
+            spans.push(WebTextSpan {
+                kind,
+                source: source.clone(),
+                target: start..result.len(),
+            });
+            last = source.end;
+        }
+        result.push_str(&text[last..]);
+
+        WebTextConversion {
+            text: result,
+            spans,
+        }
+    }
+}
+
+fn spell(kind: WebTextKind, text: &str) -> String {
+    let mut words: Vec<String> = vec![];
+    let mut rest = text;
+    match kind {
+        WebTextKind::Hashtag => {
+            words.push("ハッシュタグ".to_string());
+            rest = &text[text.chars().next().map(char::len_utf8).unwrap_or(0)..];
+        }
+        WebTextKind::Mention => {
+            words.push("アット".to_string());
+            rest = &text[text.chars().next().map(char::len_utf8).unwrap_or(0)..];
+        }
+        WebTextKind::Url | WebTextKind::Email => (),
+    }
+
+    let mut chars = rest.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if let Some(name) = symbol_name(c) {
+            words.push(name.to_string());
+            continue;
+        }
+        // a run of letters and digits, or of the other characters
+        let is_ascii = c.is_ascii_alphanumeric();
+        let mut end = start + c.len_utf8();
+        while let Some(&(i, next)) = chars.peek() {
+            if symbol_name(next).is_some() || next.is_ascii_alphanumeric() != is_ascii {
+                break;
+            }
+            end = i + next.len_utf8();
+            chars.next();
+        }
+        let word = &rest[start..end];
+        if is_ascii {
+            words.push(spell_alphanumeric(word));
+        } else {
+            words.push(word.to_string());
+        }
+    }
+
+    words.join(" ")
+}
+
+fn spell_alphanumeric(word: &str) -> String {
+    match word.to_ascii_lowercase().as_str() {
+        "com" => return "コム".to_string(),
+        "net" => return "ネット".to_string(),
+        "info" => return "インフォ".to_string(),
+        _ => (),
+    }
+    word.chars().filter_map(char_name).collect()
+}
+
+fn char_name(c: char) -> Option<&'static str> {
+    const LETTERS: [&str; 26] = [
+        "エー",
+        "ビー",
+        "シー",
+        "ディー",
+        "イー",
+        "エフ",
+        "ジー",
+        "エイチ",
+        "アイ",
+        "ジェー",
+        "ケー",
+        "エル",
+        "エム",
+        "エヌ",
+        "オー",
+        "ピー",
+        "キュー",
+        "アール",
+        "エス",
+        "ティー",
+        "ユー",
+        "ブイ",
+        "ダブリュー",
+        "エックス",
+        "ワイ",
+        "ゼット",
+    ];
+    const DIGITS: [&str; 10] = [
+        "ゼロ",
+        "イチ",
+        "ニー",
+        "サン",
+        "ヨン",
+        "ゴー",
+        "ロク",
+        "ナナ",
+        "ハチ",
+        "キュー",
+    ];
+    match c {
+        'a'..='z' => Some(LETTERS[c as usize - 'a' as usize]),
+        'A'..='Z' => Some(LETTERS[c as usize - 'A' as usize]),
+        '0'..='9' => Some(DIGITS[c as usize - '0' as usize]),
+        _ => None,
+    }
+}
+
+fn symbol_name(c: char) -> Option<&'static str> {
+    Some(match c {
+        ':' => "コロン",
+        '/' => "スラッシュ",
+        '.' => "ドット",
+        '-' => "ハイフン",
+        '_' => "アンダーバー",
+        '@' => "アットマーク",
+        '?' => "クエスチョン",
+        '=' => "イコール",
+        '&' => "アンド",
+        '#' => "シャープ",
+        '%' => "パーセント",
+        '~' => "チルダ",
+        '+' => "プラス",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WebTextKind, WebTextReader, WebTextReading};
+
+    #[test]
+    fn find() {
+        let reader = WebTextReader::default();
+        let text = "@user_1 連絡はfoo.bar@example.co.jpへ。http://a.b/c?d=1. #タグ C#";
+        let found: Vec<_> = reader
+            .find(text)
+            .into_iter()
+            .map(|(kind, range)| (kind, &text[range]))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (WebTextKind::Mention, "@user_1"),
+                (WebTextKind::Email, "foo.bar@example.co.jp"),
+                (WebTextKind::Url, "http://a.b/c?d=1"),
+                (WebTextKind::Hashtag, "#タグ"),
+            ]
+        );
+    }
+
+    #[test]
+    fn spell() {
+        let reader = WebTextReader::default();
+        assert_eq!(
+            reader.convert("me@ex.jp").text,
+            "エムイー アットマーク イーエックス ドット ジェーピー"
+        );
+        assert_eq!(
+            reader.convert("#Rust好き @a_1").text,
+            "ハッシュタグ アールユーエスティー 好き アット エー アンダーバー イチ"
+        );
+    }
+
+    #[test]
+    fn offsets() {
+        let reader = WebTextReader {
+            email: WebTextReading::Replace("メール".to_string()),
+            hashtag: WebTextReading::Keep,
+            ..Default::default()
+        };
+        let text = "宛先a@b.cc、#タグ";
+        let conversion = reader.convert(text);
+        assert_eq!(conversion.text, "宛先メール、#タグ");
+        assert_eq!(conversion.spans.len(), 1);
+        assert_eq!(conversion.spans[0].source, 6..12);
+
+        let offsets: Vec<usize> = conversion
+            .text
+            .char_indices()
+            .map(|(i, _)| conversion.source_offset(i))
+            .collect();
+        assert_eq!(offsets, [0, 3, 6, 6, 6, 12, 15, 16, 19]);
+        assert_eq!(conversion.source_offset(conversion.text.len()), text.len());
+    }
+}
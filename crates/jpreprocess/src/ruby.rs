@@ -0,0 +1,232 @@
+use std::str::FromStr;
+
+use jpreprocess_core::{
+    error::JPreprocessErrorKind, pronunciation::Pronunciation, JPreprocessResult,
+};
+use jpreprocess_njd::{NJDNode, NJD};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static HTML_RUBY_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<ruby>(.*?)</ruby>").expect("Failed to compile ruby regex"));
+static HTML_RT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)(.*?)<rt>(.*?)</rt>").expect("Failed to compile rt regex"));
+static HTML_RP_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<rp>.*?</rp>|</?rb>").expect("Failed to compile rp regex"));
+
+/// A piece of text, with the reading if annotated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RubySegment {
+    pub text: String,
+    pub reading: Option<String>,
+}
+
+/// Parses the text with ruby (furigana) markup.
+///
+/// The following forms are supported:
+/// - Aozora Bunko: `｜漢字《かんじ》`. Without `｜`, the reading applies to the run of kanji just before `《`.
+/// - HTML: `<ruby>漢字<rt>かんじ</rt></ruby>`, optionally with `<rb>` and `<rp>`.
+///
+/// The text is returned as the segments, and the adjacent segments without reading are joined.
+///
+/// ```rust
+/// use jpreprocess::{parse_ruby, RubySegment};
+///
+/// let segments = parse_ruby("｜明日香《あすか》と<ruby>飛鳥<rp>(</rp><rt>あすか</rt><rp>)</rp></ruby>へ");
+/// assert_eq!(segments[0].text, "明日香");
+/// assert_eq!(segments[0].reading.as_deref(), Some("あすか"));
+/// assert_eq!(segments[1].text, "と");
+/// assert_eq!(segments[1].reading, None);
+/// assert_eq!(segments[2].text, "飛鳥");
+/// assert_eq!(segments.len(), 4);
+/// ```
+pub fn parse_ruby(markup: &str) -> Vec<RubySegment> {
+    let mut segments = vec![];
+    let mut last = 0;
+    for captures in HTML_RUBY_REGEX.captures_iter(markup) {
+        let whole = captures.get(0).unwrap();
+        parse_aozora(&markup[last..whole.start()], &mut segments);
+
+        let inner = HTML_RP_REGEX.replace_all(&captures[1], "");
+        let mut rest = 0;
+        for rt in HTML_RT_REGEX.captures_iter(&inner) {
+            push(&mut segments, &rt[1], Some(&rt[2]));
+            rest = rt.get(0).unwrap().end();
+        }
+        push(&mut segments, &inner[rest..], None);
+
+        last = whole.end();
+    }
+    parse_aozora(&markup[last..], &mut segments);
+    segments
+}
+
+fn parse_aozora(text: &str, segments: &mut Vec<RubySegment>) {
+    let mut plain = String::new();
+    // the position of `｜` in `plain`
+    let mut marker: Option<usize> = None;
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        rest = &rest[c.len_utf8()..];
+        match c {
+            '｜' => {
+                marker = Some(plain.len());
+                continue;
+            }
+            '《' => {
+                if let Some((reading, after)) = rest.split_once('》') {
+                    let start = marker.unwrap_or_else(|| {
+                        plain
+                            .char_indices()
+                            .rev()
+                            .take_while(|(_, c)| is_kanji(*c))
+                            .last()
+                            .map(|(i, _)| i)
+                            .unwrap_or(plain.len())
+                    });
+                    if start < plain.len() && !reading.is_empty() {
+                        let base = plain.split_off(start);
+                        push(segments, &plain, None);
+                        push(segments, &base, Some(reading));
+                        plain.clear();
+                        marker = None;
+                        rest = after;
+                        continue;
+                    }
+                }
+            }
+            _ => (),
+        }
+        plain.push(c);
+    }
+    push(segments, &plain, None);
+}
+
+fn push(segments: &mut Vec<RubySegment>, text: &str, reading: Option<&str>) {
+    if text.is_empty() {
+        return;
+    }
+    // an empty reading (e.g. `<rt></rt>`) is ignored
+    let reading = reading.map(str::trim).filter(|reading| !reading.is_empty());
+    match (segments.last_mut(), reading) {
+        (Some(last), None) if last.reading.is_none() => last.text.push_str(text),
+        _ => segments.push(RubySegment {
+            text: text.to_string(),
+            reading: reading.map(str::to_string),
+        }),
+    }
+}
+
+fn is_kanji(c: char) -> bool {
+    matches!(c, '々' | '〆' | 'ヶ' | '\u{3400}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}')
+}
+
+/// Joins the nodes of an annotated word into one, and sets the reading.
+///
+/// The part of speech and conjugation are taken from the last node.
+/// The accent is kept if the word is a single node, and is set to 0 (flat) otherwise.
+pub(crate) fn set_ruby_reading(njd: NJD, reading: &str) -> JPreprocessResult<NJDNode> {
+    let pron = parse_reading(reading)?;
+    let string: String = njd.nodes.iter().map(|node| node.get_string()).collect();
+    let is_single = njd.nodes.len() == 1;
+
+    let mut node = njd.nodes.into_iter().last().ok_or_else(|| {
+        JPreprocessErrorKind::WordNotFoundError
+            .with_error(anyhow::anyhow!("No word to annotate with {}", reading))
+    })?;
+    let mora_size = pron.mora_size() as i32;
+    let acc = if is_single {
+        node.get_acc().min(mora_size)
+    } else {
+        0
+    };
+
+    node.replace_string(&string);
+    node.set_read(&pron.to_pure_string());
+    node.set_mora_size(mora_size);
+    node.set_acc(acc);
+    node.set_pron(pron);
+    Ok(node)
+}
+
+/// Parses a reading in hiragana or katakana.
+pub(crate) fn parse_reading(reading: &str) -> JPreprocessResult<Pronunciation> {
+    let katakana: String = reading
+        .chars()
+        .map(|c| match c {
+            'ぁ'..='ゖ' => char::from_u32(c as u32 + 0x60).unwrap(),
+            _ => c,
+        })
+        .collect();
+    let pron = Pronunciation::from_str(&katakana)?;
+    if pron.mora_size() == 0 || pron.to_pure_string() != katakana {
+        return Err(JPreprocessErrorKind::PronunciationParseError
+            .with_error(anyhow::anyhow!("Invalid reading {}", reading)));
+    }
+    Ok(pron)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_reading, parse_ruby, RubySegment};
+
+    fn segment(text: &str, reading: Option<&str>) -> RubySegment {
+        RubySegment {
+            text: text.to_string(),
+            reading: reading.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn aozora() {
+        assert_eq!(
+            parse_ruby("ここは東京都《とうきょうと》の｜新宿《しんじゅく》"),
+            [
+                segment("ここは", None),
+                segment("東京都", Some("とうきょうと")),
+                segment("の", None),
+                segment("新宿", Some("しんじゅく"))
+            ]
+        );
+        assert_eq!(
+            parse_ruby("佐々木《ささき》｜ＡＩ《エーアイ》"),
+            [
+                segment("佐々木", Some("ささき")),
+                segment("ＡＩ", Some("エーアイ"))
+            ]
+        );
+        // not ruby
+        assert_eq!(
+            parse_ruby("かな《かな》と《》"),
+            [segment("かな《かな》と《》", None)]
+        );
+        assert_eq!(parse_ruby("漢字《かんじ"), [segment("漢字《かんじ", None)]);
+    }
+
+    #[test]
+    fn html() {
+        assert_eq!(
+            parse_ruby("<ruby><rb>漢</rb><rt>かん</rt>字<rt>じ</rt></ruby>を<b>読む</b>"),
+            [
+                segment("漢", Some("かん")),
+                segment("字", Some("じ")),
+                segment("を<b>読む</b>", None)
+            ]
+        );
+        assert_eq!(
+            parse_ruby("<ruby>無<rt></rt></ruby>し"),
+            [segment("無し", None)]
+        );
+    }
+
+    #[test]
+    fn reading() {
+        assert_eq!(
+            parse_reading("きょうはアメ").unwrap().to_pure_string(),
+            "キョウハアメ"
+        );
+        assert!(parse_reading("").is_err());
+        assert!(parse_reading("かんじx").is_err());
+        assert!(parse_reading("漢字").is_err());
+    }
+}
@@ -0,0 +1,121 @@
+/// Splits a document into sentences.
+///
+/// The text is split after `。`, `！`, `？`, `!`, `?` and `．`, and at newlines.
+/// The sentence-ending characters inside brackets or quotes (e.g. `「はい。」と答えた。`)
+/// do not split the text, while newlines always do.
+///
+/// The consecutive sentence-ending characters and the closing brackets just after them
+/// belong to the preceding sentence.
+/// The sentences are trimmed, and the empty ones are omitted.
+///
+/// ```rust
+/// use jpreprocess::split_sentences;
+///
+/// assert_eq!(
+///     split_sentences("「行くよ。」と言った。本当？！\n（たぶん。）"),
+///     ["「行くよ。」と言った。", "本当？！", "（たぶん。）"]
+/// );
+/// ```
+pub fn split_sentences<'a>(text: &'a str) -> Vec<&'a str> {
+    let mut sentences = vec![];
+    let mut push = |sentence: &'a str| {
+        let sentence = sentence.trim();
+        if !sentence.is_empty() {
+            sentences.push(sentence);
+        }
+    };
+
+    for line in text.lines() {
+        let mut depth: usize = 0;
+        let mut start = 0;
+        let mut chars = line.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if is_open_bracket(c) {
+                depth += 1;
+                continue;
+            }
+            if is_close_bracket(c) {
+                depth = depth.saturating_sub(1);
+                continue;
+            }
+            if depth > 0 || !is_terminal(c, line[i + c.len_utf8()..].chars().next()) {
+                continue;
+            }
+
+            let mut end = i + c.len_utf8();
+            while let Some(&(j, next)) = chars.peek() {
+                if !(is_terminal(next, None) || is_close_bracket(next)) {
+                    break;
+                }
+                end = j + next.len_utf8();
+                chars.next();
+            }
+            push(&line[start..end]);
+            start = end;
+        }
+        push(&line[start..]);
+    }
+
+    sentences
+}
+
+/// Whether `c` ends a sentence. `next` is the character following `c`, if any.
+fn is_terminal(c: char, next: Option<char>) -> bool {
+    match c {
+        '。' | '！' | '？' => true,
+        // e.g. `?` in URLs
+        '!' | '?' => !next
+            .map(|next| next.is_ascii_alphanumeric())
+            .unwrap_or(false),
+        // e.g. `３．１４`
+        '．' => !next.map(|next| next.is_numeric()).unwrap_or(false),
+        _ => false,
+    }
+}
+
+fn is_open_bracket(c: char) -> bool {
+    matches!(
+        c,
+        '「' | '『' | '（' | '(' | '【' | '〔' | '［' | '[' | '《' | '〈' | '“'
+    )
+}
+
+fn is_close_bracket(c: char) -> bool {
+    matches!(
+        c,
+        '」' | '』' | '）' | ')' | '】' | '〕' | '］' | ']' | '》' | '〉' | '”'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_sentences;
+
+    #[test]
+    fn split() {
+        assert_eq!(
+            split_sentences("今日は晴れ。明日は雨！  \r\n\n明後日は？"),
+            ["今日は晴れ。", "明日は雨！", "明後日は？"]
+        );
+        assert_eq!(split_sentences("句点なし"), ["句点なし"]);
+        assert_eq!(split_sentences(" \n。"), ["。"]);
+        assert!(split_sentences("").is_empty());
+    }
+
+    #[test]
+    fn brackets() {
+        assert_eq!(
+            split_sentences("彼は『え？本当？』と聞いた。（笑）はい。"),
+            ["彼は『え？本当？』と聞いた。", "（笑）はい。"]
+        );
+        // unbalanced brackets do not affect the next line
+        assert_eq!(
+            split_sentences("「閉じない。\n次の文。その次。"),
+            ["「閉じない。", "次の文。", "その次。"]
+        );
+        assert_eq!(
+            split_sentences("３．１４です．http://a.b/?c=1を見て!"),
+            ["３．１４です．", "http://a.b/?c=1を見て!"]
+        );
+    }
+}
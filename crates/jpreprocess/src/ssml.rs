@@ -0,0 +1,490 @@
+use std::{collections::HashMap, time::Duration};
+
+use jpreprocess_core::{error::JPreprocessErrorKind, JPreprocessError, JPreprocessResult};
+use jpreprocess_njd::{NJDNode, NJD};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static TAG_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"<(/?)([A-Za-z][\w:.-]*)((?:\s+[\w:.-]+\s*=\s*(?:"[^"]*"|'[^']*'))*)\s*(/?)>"#)
+        .expect("Failed to compile tag regex")
+});
+static ATTRIBUTE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"([\w:.-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+        .expect("Failed to compile attribute regex")
+});
+static IGNORED_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<\?.*?\?>|<!--.*?-->|<!DOCTYPE[^>]*>").expect("Failed to compile regex")
+});
+static LINE_BREAK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\s*\n\s*").expect("Failed to compile line break regex"));
+
+/// The alphabet of `<phoneme>`, whose `ph` is in hiragana or katakana.
+pub const KANA_ALPHABET: &str = "x-jpreprocess-kana";
+
+/// `interpret-as` of `<say-as>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SayAs {
+    /// Reads the characters one by one.
+    Characters,
+    /// Reads the digits one by one, e.g. `123` as イチニーサン.
+    Digits,
+    /// Reads a date such as `2024/1/5` as 2024年1月5日.
+    ///
+    /// `format` is the order of the fields (e.g. `ymd`, `md` or `dmy`).
+    /// If omitted, it is `ymd` for three fields, and `md` for two.
+    Date { format: Option<String> },
+    /// Reads the digits one by one, and pauses at the separators.
+    Telephone,
+}
+
+/// A part of SSML document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SsmlSegment {
+    /// Plain text, including the `alias` of `<sub>`.
+    Text(String),
+    /// `<phoneme>`, the text read as `kana`.
+    Phoneme {
+        text: String,
+        kana: String,
+    },
+    SayAs {
+        text: String,
+        interpret_as: SayAs,
+    },
+    /// `<break>`, or the boundary of `<prosody>`, `<p>` or `<s>`.
+    ///
+    /// This is a pause, which is the boundary of breath groups.
+    /// `time` is not reflected in the labels.
+    Break {
+        time: Option<Duration>,
+    },
+}
+
+/// Parses a subset of SSML.
+///
+/// The supported elements are:
+/// - `<break time="500ms"/>` or `<break strength="..."/>`. `strength="none"` is ignored.
+/// - `<sub alias="...">`
+/// - `<say-as interpret-as="characters|digits|date|telephone">`
+/// - `<phoneme alphabet="x-jpreprocess-kana" ph="...">`
+/// - `<prosody>`, `<p>` and `<s>`, whose boundaries are regarded as breaks.
+///   The attributes of `<prosody>` are ignored.
+///
+/// The other elements, such as `<speak>`, are ignored, but their contents are read.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use jpreprocess::{parse_ssml, SayAs, SsmlSegment};
+///
+/// let segments = parse_ssml(
+///     r#"<speak>電話は<say-as interpret-as="telephone">03-1234-5678</say-as><break time="1s"/>です</speak>"#,
+/// )?;
+/// assert_eq!(
+///     segments,
+///     [
+///         SsmlSegment::Text("電話は".to_string()),
+///         SsmlSegment::SayAs {
+///             text: "03-1234-5678".to_string(),
+///             interpret_as: SayAs::Telephone
+///         },
+///         SsmlSegment::Break {
+///             time: Some(Duration::from_secs(1))
+///         },
+///         SsmlSegment::Text("です".to_string()),
+///     ]
+/// );
+/// # Ok::<(), jpreprocess_core::JPreprocessError>(())
+/// ```
+pub fn parse_ssml(ssml: &str) -> JPreprocessResult<Vec<SsmlSegment>> {
+    let ssml = IGNORED_REGEX.replace_all(ssml, "");
+
+    let mut segments = vec![];
+    // the open elements, and the text inside of the current one
+    let mut stack: Vec<(String, HashMap<String, String>)> = vec![];
+    let mut content: Option<String> = None;
+
+    let mut last = 0;
+    for captures in TAG_REGEX.captures_iter(&ssml) {
+        let tag = captures.get(0).unwrap();
+        let text = unescape(&ssml[last..tag.start()])?;
+        last = tag.end();
+        match &mut content {
+            Some(content) => content.push_str(&text),
+            None => push_text(&mut segments, &text),
+        }
+
+        let name = captures[2].to_string();
+        let is_close = !captures[1].is_empty();
+        let is_empty = !captures[4].is_empty();
+
+        if is_close {
+            let (open, attributes) = stack
+                .pop()
+                .ok_or_else(|| markup_error(format!("</{}> does not have the start tag", name)))?;
+            if open != name {
+                return Err(markup_error(format!(
+                    "</{}> does not match <{}>",
+                    name, open
+                )));
+            }
+            let text = content.take().unwrap_or_default();
+            end_element(&mut segments, &name, &attributes, text)?;
+            continue;
+        }
+
+        if content.is_some() {
+            return Err(markup_error(format!(
+                "<{}> is not allowed in <{}>",
+                name,
+                stack.last().map(|(name, _)| name.as_str()).unwrap_or("")
+            )));
+        }
+        let attributes = parse_attributes(&captures[3])?;
+        if is_empty {
+            match name.as_str() {
+                "break" => push_break(&mut segments, break_time(&attributes)?),
+                "sub" | "say-as" | "phoneme" => {
+                    end_element(&mut segments, &name, &attributes, String::new())?
+                }
+                _ => (),
+            }
+            continue;
+        }
+        match name.as_str() {
+            "sub" | "say-as" | "phoneme" => content = Some(String::new()),
+            "prosody" | "p" | "s" => push_break(&mut segments, Some(None)),
+            _ => (),
+        }
+        stack.push((name, attributes));
+    }
+    if let Some((name, _)) = stack.last() {
+        return Err(markup_error(format!("<{}> is not closed", name)));
+    }
+    push_text(&mut segments, &unescape(&ssml[last..])?);
+
+    Ok(segments)
+}
+
+fn end_element(
+    segments: &mut Vec<SsmlSegment>,
+    name: &str,
+    attributes: &HashMap<String, String>,
+    text: String,
+) -> JPreprocessResult<()> {
+    let attribute = |key: &str| {
+        attributes
+            .get(key)
+            .ok_or_else(|| markup_error(format!("<{}> requires {}", name, key)))
+    };
+    match name {
+        "sub" => push_text(segments, attribute("alias")?),
+        "phoneme" => {
+            let alphabet = attribute("alphabet")?;
+            if alphabet != KANA_ALPHABET {
+                return Err(markup_error(format!(
+                    "Unsupported alphabet {}. Use {}",
+                    alphabet, KANA_ALPHABET
+                )));
+            }
+            segments.push(SsmlSegment::Phoneme {
+                text: normalize_whitespace(&text),
+                kana: attribute("ph")?.to_string(),
+            });
+        }
+        "say-as" => {
+            let interpret_as = match attribute("interpret-as")?.as_str() {
+                "characters" => SayAs::Characters,
+                "digits" => SayAs::Digits,
+                "date" => SayAs::Date {
+                    format: attributes.get("format").cloned(),
+                },
+                "telephone" => SayAs::Telephone,
+                other => {
+                    return Err(markup_error(format!("Unsupported interpret-as {}", other)));
+                }
+            };
+            segments.push(SsmlSegment::SayAs {
+                text: normalize_whitespace(&text),
+                interpret_as,
+            });
+        }
+        "prosody" | "p" | "s" => push_break(segments, Some(None)),
+        _ => (),
+    }
+    Ok(())
+}
+
+fn push_text(segments: &mut Vec<SsmlSegment>, text: &str) {
+    let text = normalize_whitespace(text);
+    if text.is_empty() {
+        return;
+    }
+    match segments.last_mut() {
+        Some(SsmlSegment::Text(last)) => last.push_str(&text),
+        _ => segments.push(SsmlSegment::Text(text)),
+    }
+}
+
+/// Pushes a break unless `time` is `None`. Consecutive breaks are merged into the longest.
+fn push_break(segments: &mut Vec<SsmlSegment>, time: Option<Option<Duration>>) {
+    let Some(time) = time else {
+        return;
+    };
+    match segments.last_mut() {
+        Some(SsmlSegment::Break { time: last }) => *last = (*last).max(time),
+        _ => segments.push(SsmlSegment::Break { time }),
+    }
+}
+
+/// Returns `None` if the break is disabled, e.g. `strength="none"`.
+fn break_time(attributes: &HashMap<String, String>) -> JPreprocessResult<Option<Option<Duration>>> {
+    if let Some(time) = attributes.get("time") {
+        let time = time.trim();
+        let (number, scale) = if let Some(ms) = time.strip_suffix("ms") {
+            (ms, 0.001)
+        } else if let Some(s) = time.strip_suffix('s') {
+            (s, 1.0)
+        } else {
+            return Err(markup_error(format!("Invalid time {}", time)));
+        };
+        let seconds: f64 = number
+            .trim()
+            .parse()
+            .map_err(|_| markup_error(format!("Invalid time {}", time)))?;
+        if !(seconds.is_finite() && seconds >= 0.0) {
+            return Err(markup_error(format!("Invalid time {}", time)));
+        }
+        let duration = Duration::from_secs_f64(seconds * scale);
+        return Ok((!duration.is_zero()).then_some(Some(duration)));
+    }
+    match attributes.get("strength").map(String::as_str) {
+        Some("none") => Ok(None),
+        _ => Ok(Some(None)),
+    }
+}
+
+fn parse_attributes(attributes: &str) -> JPreprocessResult<HashMap<String, String>> {
+    ATTRIBUTE_REGEX
+        .captures_iter(attributes)
+        .map(|captures| {
+            let value = captures.get(2).or_else(|| captures.get(3)).unwrap();
+            Ok((captures[1].to_string(), unescape(value.as_str())?))
+        })
+        .collect()
+}
+
+fn unescape(text: &str) -> JPreprocessResult<String> {
+    if text.contains('<') {
+        return Err(markup_error(format!("Invalid tag in {}", text)));
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find(';')
+            .ok_or_else(|| markup_error(format!("Unterminated entity in {}", text)))?;
+        let entity = &rest[start + 1..start + end];
+        let c = match entity {
+            "lt" => '<',
+            "gt" => '>',
+            "amp" => '&',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32)
+                .ok_or_else(|| markup_error(format!("Unknown entity &{};", entity)))?,
+        };
+        result.push(c);
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Removes the line breaks and indents between the elements.
+fn normalize_whitespace(text: &str) -> String {
+    LINE_BREAK_REGEX.replace_all(text, "").into_owned()
+}
+
+pub(crate) fn markup_error(message: String) -> JPreprocessError {
+    JPreprocessErrorKind::MarkupParseError.with_error(anyhow::anyhow!(message))
+}
+
+/// Appends a node which makes a pause.
+///
+/// A pause at the beginning or just after another pause is not appended,
+/// as it makes an empty breath group.
+pub(crate) fn push_pause(nodes: &mut Vec<NJDNode>) {
+    if nodes
+        .last()
+        .map(|node| !node.get_pron().is_touten())
+        .unwrap_or(false)
+    {
+        nodes.push(NJDNode::new_single("、,記号,読点,*,*,*,*,、,、,、,0/0,*"));
+    }
+}
+
+/// Converts the text of `<say-as>` into NJD nodes with `text_to_njd`.
+pub(crate) fn say_as_to_nodes<F>(
+    text: &str,
+    interpret_as: &SayAs,
+    text_to_njd: F,
+) -> JPreprocessResult<Vec<NJDNode>>
+where
+    F: Fn(&str) -> JPreprocessResult<NJD>,
+{
+    let (mut nodes, numerical_reading) = match interpret_as {
+        SayAs::Characters => {
+            let mut nodes = vec![];
+            for c in text.chars().filter(|c| !c.is_whitespace()) {
+                nodes.extend(text_to_njd(c.encode_utf8(&mut [0; 4]))?.nodes);
+            }
+            (nodes, false)
+        }
+        SayAs::Digits => (text_to_njd(text)?.nodes, false),
+        SayAs::Date { format } => (
+            text_to_njd(&date_text(text, format.as_deref())?)?.nodes,
+            true,
+        ),
+        SayAs::Telephone => {
+            let mut nodes = vec![];
+            for part in text
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|part| !part.is_empty())
+            {
+                push_pause(&mut nodes);
+                nodes.extend(text_to_njd(part)?.nodes);
+            }
+            (nodes, false)
+        }
+    };
+    for node in &mut nodes {
+        node.set_numerical_reading(numerical_reading);
+    }
+    Ok(nodes)
+}
+
+/// Converts a date like `2024/1/5` into `2024年1月5日`.
+fn date_text(text: &str, format: Option<&str>) -> JPreprocessResult<String> {
+    let fields: Vec<u32> = text
+        .split(|c: char| !c.is_numeric())
+        .filter(|field| !field.is_empty())
+        .map(|field| {
+            field
+                .chars()
+                .try_fold(0u32, |value, c| {
+                    c.to_digit(10)
+                        .or_else(|| ('０'..='９').contains(&c).then(|| c as u32 - '０' as u32))
+                        .and_then(|digit| value.checked_mul(10)?.checked_add(digit))
+                })
+                .ok_or_else(|| markup_error(format!("Invalid date {}", text)))
+        })
+        .collect::<JPreprocessResult<_>>()?;
+    let format = match (format, fields.len()) {
+        (Some(format), _) => format,
+        (None, 3) => "ymd",
+        (None, 2) => "md",
+        _ => return Err(markup_error(format!("Invalid date {}", text))),
+    };
+    if format.len() != fields.len() {
+        return Err(markup_error(format!(
+            "Date {} does not match format {}",
+            text, format
+        )));
+    }
+
+    let mut result = String::new();
+    for (field, suffix) in [('y', "年"), ('m', "月"), ('d', "日")] {
+        if let Some(i) = format.find(field) {
+            result.push_str(&format!("{}{}", fields[i], suffix));
+        }
+    }
+    if result.chars().filter(|c| !c.is_ascii_digit()).count() != format.len() {
+        return Err(markup_error(format!("Invalid date format {}", format)));
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use jpreprocess_core::error::JPreprocessErrorKind;
+
+    use super::{date_text, parse_ssml, SayAs, SsmlSegment};
+
+    fn text(text: &str) -> SsmlSegment {
+        SsmlSegment::Text(text.to_string())
+    }
+
+    #[test]
+    fn elements() {
+        let segments = parse_ssml(
+            r#"<?xml version="1.0"?>
+            <speak>
+              <p><sub alias="ダブリュー ダブリュー ダブリュー">WWW</sub>の&lt;仕様&gt;</p>
+              <prosody rate="fast"><phoneme alphabet="x-jpreprocess-kana" ph="にっぽん">日本</phoneme></prosody>
+              <break strength="none"/>
+              <!-- comment -->
+              <say-as interpret-as='date' format="md">1/5</say-as><break time="200ms"/><break time="0.5s"/>
+            </speak>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            segments,
+            [
+                SsmlSegment::Break { time: None },
+                text("ダブリュー ダブリュー ダブリューの<仕様>"),
+                SsmlSegment::Break { time: None },
+                SsmlSegment::Phoneme {
+                    text: "日本".to_string(),
+                    kana: "にっぽん".to_string()
+                },
+                SsmlSegment::Break { time: None },
+                SsmlSegment::SayAs {
+                    text: "1/5".to_string(),
+                    interpret_as: SayAs::Date {
+                        format: Some("md".to_string())
+                    }
+                },
+                SsmlSegment::Break {
+                    time: Some(Duration::from_millis(500))
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        for ssml in [
+            "<speak>開いたまま",
+            "<speak></p>",
+            "<p></s>",
+            r#"<phoneme alphabet="ipa" ph="a">あ</phoneme>"#,
+            r#"<say-as interpret-as="currency">1</say-as>"#,
+            r#"<sub alias="a"><break/></sub>"#,
+            r#"<break time="fast"/>"#,
+            "a < b",
+            "&unknown;",
+        ] {
+            let err = parse_ssml(ssml).unwrap_err();
+            assert_eq!(err.kind, JPreprocessErrorKind::MarkupParseError, "{}", ssml);
+        }
+    }
+
+    #[test]
+    fn date() {
+        assert_eq!(date_text("2024/01/05", None).unwrap(), "2024年1月5日");
+        assert_eq!(date_text("１２-２５", None).unwrap(), "12月25日");
+        assert_eq!(date_text("5.1.2024", Some("dmy")).unwrap(), "2024年1月5日");
+        assert!(date_text("2024", None).is_err());
+        assert!(date_text("1/2", Some("ymd")).is_err());
+        assert!(date_text("1/2", Some("yy")).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use jpreprocess_core::{word_entry::WordEntry, JPreprocessResult};
+use jpreprocess_dictionary::{
+    metadata::detect_dictionary, overrides::WordOverrides, WordDictionaryConfig,
+};
+use lindera_core::{
+    dictionary::{Dictionary, UserDictionary},
+    mode::Mode,
+    viterbi::Lattice,
+    word_entry::WordId,
+};
+use lindera_tokenizer::token::Token;
+
+use crate::{OffsetMap, TextNormalizer};
+
+use super::{
+    nbest::{nbest_paths, split_sentences},
+    Segmentation, Tokenizer,
+};
+
+/// Tokenizer using lindera with a lindera/jpreprocess dictionary.
+///
+/// The text is normalized with [`TextNormalizer`] before tokenization.
+/// By default, it is the same as [`normalize_text_for_naist_jdic`](crate::normalize_text_for_naist_jdic).
+pub struct LinderaTokenizer {
+    dictionary: Dictionary,
+    user_dictionary: Option<UserDictionary>,
+    mode: Mode,
+    normalizer: TextNormalizer,
+    dictionary_config: WordDictionaryConfig,
+    overrides: WordOverrides,
+}
+
+impl LinderaTokenizer {
+    pub fn new(
+        dictionary: Dictionary,
+        user_dictionary: Option<UserDictionary>,
+        mode: Mode,
+    ) -> Self {
+        let dictionary_config = WordDictionaryConfig {
+            system: detect_dictionary(&dictionary.words_idx_data, &dictionary.words_data),
+            user: user_dictionary.as_ref().map(|user_dictionary| {
+                detect_dictionary(&user_dictionary.words_idx_data, &user_dictionary.words_data)
+            }),
+        };
+
+        Self {
+            dictionary,
+            user_dictionary,
+            mode,
+            normalizer: TextNormalizer::default(),
+            dictionary_config,
+            overrides: WordOverrides::default(),
+        }
+    }
+
+    pub fn set_normalizer(&mut self, normalizer: TextNormalizer) {
+        self.normalizer = normalizer;
+    }
+
+    pub fn set_word_overrides(&mut self, overrides: WordOverrides) {
+        self.overrides = overrides;
+    }
+
+    fn get_word(&self, token: &Token) -> JPreprocessResult<WordEntry> {
+        let mut entry = self.dictionary_config.get_word(token)?;
+        if !self.overrides.is_empty() {
+            self.overrides.apply(token.word_id, token.text, &mut entry);
+        }
+        Ok(entry)
+    }
+
+    /// Finds the best path with the lattice of lindera, in the same way as
+    /// `lindera_tokenizer::tokenizer::Tokenizer::tokenize`.
+    fn best_path(&self, text: &str) -> Vec<(usize, WordId)> {
+        let mut lattice = Lattice::default();
+        let mut offsets = Vec::new();
+        let mut byte_position = 0;
+
+        for sentence in split_sentences(text) {
+            lattice.set_text(
+                &self.dictionary.dict,
+                &self.user_dictionary.as_ref().map(|d| &d.dict),
+                &self.dictionary.char_definitions,
+                &self.dictionary.unknown_dictionary,
+                sentence,
+                &self.mode,
+            );
+            lattice.calculate_path_costs(&self.dictionary.cost_matrix, &self.mode);
+            offsets.extend(
+                lattice
+                    .tokens_offset()
+                    .into_iter()
+                    .map(|(start, word_id)| (start + byte_position, word_id)),
+            );
+
+            byte_position += sentence.len();
+        }
+
+        offsets
+    }
+
+    /// Looks up the words starting at the byte offsets of a path.
+    fn get_words(
+        &self,
+        text: &str,
+        offsets: &[(usize, WordId)],
+    ) -> JPreprocessResult<Vec<(String, WordEntry)>> {
+        offsets
+            .iter()
+            .enumerate()
+            .map(|(position, &(start, word_id))| {
+                let end = offsets
+                    .get(position + 1)
+                    .map(|(next_start, _)| *next_start)
+                    .unwrap_or(text.len());
+                let token = Token::new(
+                    &text[start..end],
+                    start,
+                    end,
+                    position,
+                    word_id,
+                    &self.dictionary,
+                    self.user_dictionary.as_ref(),
+                );
+                let entry = self.get_word(&token)?;
+                Ok((token.text.to_string(), entry))
+            })
+            .collect()
+    }
+}
+
+impl Tokenizer for LinderaTokenizer {
+    fn tokenize(&self, text: &str) -> JPreprocessResult<Vec<(String, WordEntry)>> {
+        let normalized_input_text = self.normalizer.normalize(text);
+        let offsets = self.best_path(&normalized_input_text);
+        self.get_words(&normalized_input_text, &offsets)
+    }
+
+    fn normalize(&self, text: &str) -> String {
+        self.normalizer.normalize(text)
+    }
+
+    fn normalize_with_offsets(&self, text: &str) -> (String, OffsetMap) {
+        self.normalizer.normalize_with_offsets(text)
+    }
+
+    fn tokenize_nbest(&self, text: &str, n: usize) -> JPreprocessResult<Vec<Segmentation>> {
+        let normalized_input_text = self.normalizer.normalize(text);
+        nbest_paths(
+            &self.dictionary,
+            self.user_dictionary.as_ref(),
+            &self.mode,
+            &normalized_input_text,
+            n,
+        )
+        .into_iter()
+        .map(|path| {
+            Ok(Segmentation {
+                cost: path.cost,
+                words: self.get_words(&normalized_input_text, &path.offsets)?,
+            })
+        })
+        .collect()
+    }
+}
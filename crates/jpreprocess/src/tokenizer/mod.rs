@@ -0,0 +1,87 @@
+mod lindera;
+mod nbest;
+
+pub use self::lindera::LinderaTokenizer;
+
+use jpreprocess_core::{word_entry::WordEntry, JPreprocessResult};
+
+use crate::OffsetMap;
+
+/// Morphological analyzer which splits a text into the words of NJD.
+///
+/// [`LinderaTokenizer`] is used by default.
+/// Implement this to run jpreprocess on other analyzers or on a pre-tokenized input.
+/// The text is passed as is, without the normalization done by [`LinderaTokenizer`].
+///
+/// ```rust
+/// # use std::error::Error;
+/// use jpreprocess::{tokenizer::Tokenizer, JPreprocess};
+/// use jpreprocess_core::{word_entry::WordEntry, JPreprocessResult};
+///
+/// /// Reads `surface,details` per line, as in a dictionary csv without context ids and cost.
+/// struct CsvTokenizer;
+///
+/// impl Tokenizer for CsvTokenizer {
+///     fn tokenize(&self, text: &str) -> JPreprocessResult<Vec<(String, WordEntry)>> {
+///         text.lines()
+///             .map(|line| {
+///                 let mut columns: Vec<&str> = line.split(',').collect();
+///                 columns.resize(13, "");
+///                 Ok((columns[0].to_string(), WordEntry::load(&columns[1..])?))
+///             })
+///             .collect()
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let jpreprocess = JPreprocess::with_tokenizer(CsvTokenizer);
+/// let labels = jpreprocess.extract_fullcontext(
+///     "音声,名詞,一般,*,*,*,*,音声,オンセイ,オンセー,1/4,C1\n\
+///      合成,名詞,サ変接続,*,*,*,*,合成,ゴウセイ,ゴーセー,0/4,C2",
+/// )?;
+/// assert!(labels[1].starts_with("xx^sil-o+N=s"));
+/// #     Ok(())
+/// # }
+/// ```
+pub trait Tokenizer {
+    /// Splits the text into words, and returns the surface and the dictionary entry of each word.
+    fn tokenize(&self, text: &str) -> JPreprocessResult<Vec<(String, WordEntry)>>;
+
+    /// Normalizes the text in the same way as [`Tokenizer::tokenize`], so that the surfaces
+    /// of the words can be mapped to the parts of the text.
+    ///
+    /// By default, the text of [`Tokenizer::normalize_with_offsets`] is returned.
+    fn normalize(&self, text: &str) -> String {
+        self.normalize_with_offsets(text).0
+    }
+
+    /// Same as [`Tokenizer::normalize`], but also returns the map of the offsets
+    /// from the normalized text to the input text.
+    ///
+    /// By default, the text is returned as is. Override this if the tokenizer normalizes the text.
+    fn normalize_with_offsets(&self, text: &str) -> (String, OffsetMap) {
+        (text.to_string(), OffsetMap::default())
+    }
+
+    /// Returns up to `n` candidates of the tokenization, the best first.
+    ///
+    /// This is useful for the texts with ambiguous readings, e.g. `今日` (キョウ or コンニチ).
+    /// By default, only the result of [`Tokenizer::tokenize`] is returned, with the cost of 0.
+    fn tokenize_nbest(&self, text: &str, n: usize) -> JPreprocessResult<Vec<Segmentation>> {
+        if n == 0 {
+            return Ok(vec![]);
+        }
+        Ok(vec![Segmentation {
+            cost: 0,
+            words: self.tokenize(text)?,
+        }])
+    }
+}
+
+/// A candidate returned by [`Tokenizer::tokenize_nbest`].
+#[derive(Clone, Debug)]
+pub struct Segmentation {
+    /// Total cost of the words and their connections. Lower is better.
+    pub cost: i32,
+    pub words: Vec<(String, WordEntry)>,
+}
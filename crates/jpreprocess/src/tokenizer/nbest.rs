@@ -0,0 +1,245 @@
+//! N-best search over the lattice of lindera.
+//!
+//! This mirrors the lattice construction of lindera-core 0.27.1 (`viterbi::Lattice::set_text`)
+//! and the sentence splitting of lindera-tokenizer 0.27.0 (`tokenizer::Tokenizer::tokenize`),
+//! because they do not expose the lattice. It must be kept in sync when lindera is upgraded;
+//! both are pinned in `Cargo.toml` for this reason.
+
+use lindera_core::{
+    dictionary::{Dictionary, UserDictionary},
+    mode::Mode,
+    viterbi::{Edge, EdgeType},
+    word_entry::WordId,
+};
+
+/// A path through the lattice of a text.
+pub(crate) struct Path {
+    pub cost: i32,
+    /// Byte offset and word id of each word.
+    pub offsets: Vec<(usize, WordId)>,
+}
+
+/// Splits the text into sentences, as lindera-tokenizer does before building the lattice.
+pub(crate) fn split_sentences(text: &str) -> impl Iterator<Item = &str> {
+    text.split_inclusive(&['。', '、'])
+}
+
+/// Searches up to `n` paths of the text with the lowest cost, the best first.
+///
+/// The lattice is built in the same way as lindera, so the best path matches
+/// the result of lindera's tokenizer.
+pub(crate) fn nbest_paths(
+    dictionary: &Dictionary,
+    user_dictionary: Option<&UserDictionary>,
+    mode: &Mode,
+    text: &str,
+    n: usize,
+) -> Vec<Path> {
+    if n == 0 {
+        return vec![];
+    }
+
+    let mut paths = vec![Path {
+        cost: 0,
+        offsets: vec![],
+    }];
+    let mut byte_position = 0;
+    for sentence in split_sentences(text) {
+        let lattice = Lattice::new(dictionary, user_dictionary, mode, sentence);
+        let sentence_paths = lattice.nbest(dictionary, mode, n);
+
+        let mut combined: Vec<Path> = paths
+            .iter()
+            .flat_map(|path| {
+                sentence_paths.iter().map(move |sentence_path| Path {
+                    cost: path.cost + sentence_path.cost,
+                    offsets: path
+                        .offsets
+                        .iter()
+                        .copied()
+                        .chain(
+                            sentence_path
+                                .offsets
+                                .iter()
+                                .map(|(start, word_id)| (start + byte_position, *word_id)),
+                        )
+                        .collect(),
+                })
+            })
+            .collect();
+        combined.sort_by_key(|path| path.cost);
+        combined.truncate(n);
+        paths = combined;
+
+        byte_position += sentence.len();
+    }
+
+    paths
+}
+
+const BOS: usize = 0;
+const EOS: usize = 1;
+
+struct Lattice {
+    edges: Vec<Edge>,
+    starts_at: Vec<Vec<usize>>,
+    ends_at: Vec<Vec<usize>>,
+}
+
+impl Lattice {
+    /// Port of `lindera_core::viterbi::Lattice::set_text` of lindera-core 0.27.1.
+    fn new(
+        dictionary: &Dictionary,
+        user_dictionary: Option<&UserDictionary>,
+        mode: &Mode,
+        text: &str,
+    ) -> Self {
+        let len = text.len();
+        let mut lattice = Self {
+            edges: vec![Edge::default(), Edge::default()],
+            starts_at: vec![vec![]; len + 1],
+            ends_at: vec![vec![]; len + 1],
+        };
+        lattice.ends_at[0].push(BOS);
+        lattice.starts_at[len].push(EOS);
+
+        // index of the last character of unknown word
+        let mut unknown_word_end: Option<usize> = None;
+
+        for start in 0..len {
+            if lattice.ends_at[start].is_empty() {
+                continue;
+            }
+
+            let suffix = &text[start..];
+            let mut found = false;
+
+            let prefix_dicts = user_dictionary
+                .map(|user_dictionary| &user_dictionary.dict)
+                .into_iter()
+                .chain([&dictionary.dict]);
+            for prefix_dict in prefix_dicts {
+                for (prefix_len, word_entry) in prefix_dict.prefix(suffix) {
+                    lattice.add_edge(Edge {
+                        edge_type: EdgeType::KNOWN,
+                        word_entry,
+                        left_edge: None,
+                        start_index: start as u32,
+                        stop_index: (start + prefix_len) as u32,
+                        path_cost: i32::MAX,
+                        kanji_only: is_kanji_only(&suffix[..prefix_len]),
+                    });
+                    found = true;
+                }
+            }
+
+            // In the normal mode, unknown words are not processed greedily.
+            if !mode.is_search() && unknown_word_end.map(|end| end > start).unwrap_or(false) {
+                continue;
+            }
+            let Some(first_char) = suffix.chars().next() else {
+                continue;
+            };
+            let char_definitions = &dictionary.char_definitions;
+            for (category_ord, &category) in char_definitions
+                .lookup_categories(first_char)
+                .iter()
+                .enumerate()
+            {
+                let category_data = char_definitions.lookup_definition(category);
+                if !category_data.invoke && found {
+                    continue;
+                }
+
+                let mut num_chars = 1;
+                if category_data.group {
+                    num_chars += suffix
+                        .chars()
+                        .skip(1)
+                        .take_while(|c| {
+                            char_definitions.lookup_categories(*c).get(category_ord)
+                                == Some(&category)
+                        })
+                        .count();
+                }
+                let unknown_word: String = suffix.chars().take(num_chars).collect();
+
+                for &word_id in dictionary.unknown_dictionary.lookup_word_ids(category) {
+                    lattice.add_edge(Edge {
+                        edge_type: EdgeType::UNKNOWN,
+                        word_entry: dictionary.unknown_dictionary.word_entry(word_id),
+                        left_edge: None,
+                        start_index: start as u32,
+                        stop_index: (start + unknown_word.len()) as u32,
+                        path_cost: i32::MAX,
+                        kanji_only: is_kanji_only(&unknown_word),
+                    });
+                }
+                unknown_word_end = Some(start + unknown_word.len());
+            }
+        }
+
+        lattice
+    }
+
+    fn add_edge(&mut self, edge: Edge) {
+        let id = self.edges.len();
+        self.starts_at[edge.start_index as usize].push(id);
+        self.ends_at[edge.stop_index as usize].push(id);
+        self.edges.push(edge);
+    }
+
+    /// Keeps up to `n` best paths to each edge, as the pairs of the cost and
+    /// the previous edge with the rank of the path to it.
+    fn nbest(&self, dictionary: &Dictionary, mode: &Mode, n: usize) -> Vec<Path> {
+        let mut best: Vec<Vec<(i32, usize, usize)>> = vec![vec![]; self.edges.len()];
+        best[BOS].push((0, BOS, 0));
+
+        for (left_edges, right_edges) in self.ends_at.iter().zip(&self.starts_at) {
+            for &right in right_edges {
+                let right_entry = &self.edges[right].word_entry;
+                let mut candidates: Vec<(i32, usize, usize)> = left_edges
+                    .iter()
+                    .flat_map(|&left| {
+                        let left_edge = &self.edges[left];
+                        let cost = dictionary
+                            .cost_matrix
+                            .cost(left_edge.word_entry.right_id(), right_entry.left_id())
+                            + mode.penalty_cost(left_edge);
+                        best[left]
+                            .iter()
+                            .enumerate()
+                            .map(move |(rank, (path_cost, _, _))| (path_cost + cost, left, rank))
+                    })
+                    .collect();
+                // stable, so that the best path is the same as lindera on ties
+                candidates.sort_by_key(|(cost, _, _)| *cost);
+                candidates.truncate(n);
+                for candidate in &mut candidates {
+                    candidate.0 += right_entry.word_cost as i32;
+                }
+                best[right] = candidates;
+            }
+        }
+
+        best[EOS]
+            .iter()
+            .map(|&(cost, mut edge, mut rank)| {
+                let mut offsets = vec![];
+                while edge != BOS {
+                    offsets.push((
+                        self.edges[edge].start_index as usize,
+                        self.edges[edge].word_entry.word_id,
+                    ));
+                    (_, edge, rank) = best[edge][rank];
+                }
+                offsets.reverse();
+                Path { cost, offsets }
+            })
+            .collect()
+    }
+}
+
+fn is_kanji_only(s: &str) -> bool {
+    s.chars().all(|c| (19968..=40879).contains(&(c as u32)))
+}
@@ -0,0 +1,53 @@
+use std::{error::Error, path::PathBuf};
+
+use jpreprocess::{JPreprocess, JPreprocessConfig, SystemDictionaryConfig};
+
+fn min_dict() -> Result<JPreprocess, Box<dyn Error>> {
+    Ok(JPreprocess::from_config(JPreprocessConfig {
+        dictionary: SystemDictionaryConfig::File(PathBuf::from("tests/min-dict")),
+        user_dictionary: None,
+        tokenizer: Default::default(),
+    })?)
+}
+
+fn chain_flags(njd: &jpreprocess::NJD) -> Vec<Option<bool>> {
+    njd.nodes.iter().map(|node| node.get_chain_flag()).collect()
+}
+
+#[test]
+fn accent_phrase_boundary() -> Result<(), Box<dyn Error>> {
+    let jpreprocess = min_dict()?;
+
+    let mut plain = jpreprocess.annotated_text_to_njd("音声合成エンジン")?;
+    plain.preprocess();
+    assert_eq!(chain_flags(&plain), [None, Some(true), Some(true)]);
+
+    let mut njd = jpreprocess.annotated_text_to_njd("音声|合成エンジン")?;
+    njd.preprocess();
+    assert_eq!(chain_flags(&njd), [None, Some(false), Some(true)]);
+    assert_eq!(njd.nodes[0].get_acc(), 1);
+
+    let mut njd = jpreprocess.annotated_text_to_njd("音声/合成エンジン")?;
+    njd.preprocess();
+    assert_eq!(njd.nodes.len(), 4);
+    assert!(njd.nodes[1].get_pron().is_touten());
+
+    Ok(())
+}
+
+#[test]
+fn forced_accent() -> Result<(), Box<dyn Error>> {
+    let jpreprocess = min_dict()?;
+
+    let mut njd =
+        jpreprocess.annotated_text_to_njd("{音声/オンセ'ー}|{合成エンジン/ゴーセーエ'ンジン}")?;
+    njd.preprocess();
+    assert_eq!(njd.nodes.len(), 2);
+    assert_eq!(chain_flags(&njd), [Some(false), Some(false)]);
+    assert_eq!(njd.nodes[0].get_pron().to_string(), "オンセー");
+    assert_eq!(njd.nodes[0].get_acc(), 3);
+    assert_eq!(njd.nodes[1].get_string(), "合成エンジン");
+    assert_eq!(njd.nodes[1].get_acc(), 5);
+
+    Ok(())
+}
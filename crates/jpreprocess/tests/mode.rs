@@ -0,0 +1,131 @@
+use std::{error::Error, path::PathBuf};
+
+use jpreprocess::{
+    JPreprocess, JPreprocessConfig, Mode, Penalty, SystemDictionaryConfig, TokenizerOptions, NJD,
+};
+use jpreprocess_dictionary_builder::{
+    ipadic_builder::IpadicBuilder, serializer::JPreprocessSerializer,
+};
+use lindera_dictionary::UserDictionaryConfig;
+
+/// A compound word, which is also made of `音声`, `合成` and `エンジン` in min-dict.
+fn compound_rows() -> Vec<Vec<&'static str>> {
+    vec![vec![
+        "音声合成エンジン",
+        "1345",
+        "1345",
+        "5000",
+        "名詞",
+        "一般",
+        "*",
+        "*",
+        "*",
+        "*",
+        "音声合成エンジン",
+        "オンセイゴウセイエンジン",
+        "オンセーゴーセーエンジン",
+        "5/11",
+        "C1",
+    ]]
+}
+
+fn decompose_mode() -> Mode {
+    // The compound (8 characters) is penalized, but `エンジン` (4 characters) is not.
+    Mode::Decompose(Penalty {
+        other_penalty_length_threshold: 4,
+        other_penalty_length_penalty: 10000,
+        ..Default::default()
+    })
+}
+
+fn preprocess(mode: Mode) -> Result<NJD, Box<dyn Error>> {
+    let dictionary = SystemDictionaryConfig::File(PathBuf::from("tests/min-dict")).load()?;
+    let user_dictionary = IpadicBuilder::new(Box::new(JPreprocessSerializer))
+        .build_user_dict_from_data(&compound_rows())?;
+
+    let jpreprocess = JPreprocess::with_tokenizer(jpreprocess::tokenizer::LinderaTokenizer::new(
+        dictionary,
+        Some(user_dictionary),
+        mode,
+    ));
+    let mut njd = jpreprocess.text_to_njd("音声合成エンジン")?;
+    njd.preprocess();
+    Ok(njd)
+}
+
+fn strings(njd: &NJD) -> Vec<&str> {
+    njd.nodes.iter().map(|node| node.get_string()).collect()
+}
+
+#[test]
+fn normal() -> Result<(), Box<dyn Error>> {
+    let njd = preprocess(Mode::Normal)?;
+
+    assert_eq!(strings(&njd), ["音声合成エンジン"]);
+    assert_eq!(njd.nodes[0].get_acc(), 5);
+
+    Ok(())
+}
+
+#[test]
+fn decompose() -> Result<(), Box<dyn Error>> {
+    let njd = preprocess(decompose_mode())?;
+
+    assert_eq!(strings(&njd), ["音声", "合成", "エンジン"]);
+    // The words are chained into a single accent phrase.
+    assert_eq!(
+        njd.nodes
+            .iter()
+            .map(|node| node.get_chain_flag())
+            .collect::<Vec<_>>(),
+        [None, Some(true), Some(true)]
+    );
+    // By C1 of `エンジン`, the accent nucleus is on `エ` (the 9th mora of the phrase),
+    // instead of the 5th mora given to the compound word.
+    assert_eq!(njd.nodes[0].get_acc(), 9);
+
+    Ok(())
+}
+
+#[test]
+fn default_penalty_keeps_compound() -> Result<(), Box<dyn Error>> {
+    // The default penalty (1700 per character over 7 for non-kanji words)
+    // is not enough to split this compound.
+    let njd = preprocess(Mode::Decompose(Penalty::default()))?;
+
+    assert_eq!(strings(&njd), ["音声合成エンジン"]);
+
+    Ok(())
+}
+
+#[test]
+fn decompose_from_config() -> Result<(), Box<dyn Error>> {
+    let dir = std::env::temp_dir().join(format!("jpreprocess-mode-{}", std::process::id()));
+    let user_dictionary_path = dir.join("user.bin");
+    let rows = compound_rows()
+        .into_iter()
+        .map(|row| row.into_iter().map(String::from).collect())
+        .collect();
+    IpadicBuilder::new(Box::new(JPreprocessSerializer))
+        .build_user_dictionary_from_rows(&rows, &user_dictionary_path)?;
+
+    let jpreprocess = JPreprocess::from_config(JPreprocessConfig {
+        dictionary: SystemDictionaryConfig::File(PathBuf::from("tests/min-dict")),
+        user_dictionary: Some(UserDictionaryConfig {
+            path: user_dictionary_path,
+            kind: None,
+        }),
+        tokenizer: TokenizerOptions {
+            mode: decompose_mode(),
+            ..Default::default()
+        },
+    });
+    std::fs::remove_dir_all(&dir)?;
+
+    let mut njd = jpreprocess?.text_to_njd("音声合成エンジン")?;
+    njd.preprocess();
+    assert_eq!(njd.nodes, preprocess(decompose_mode())?.nodes);
+    assert_eq!(strings(&njd), ["音声", "合成", "エンジン"]);
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use std::{error::Error, path::PathBuf};
+
+use jpreprocess::{JPreprocess, JPreprocessConfig, SystemDictionaryConfig};
+
+fn min_dict() -> Result<JPreprocess, Box<dyn Error>> {
+    Ok(JPreprocess::from_config(JPreprocessConfig {
+        dictionary: SystemDictionaryConfig::File(PathBuf::from("tests/min-dict")),
+        user_dictionary: None,
+        tokenizer: Default::default(),
+    })?)
+}
+
+#[test]
+fn best_is_same_as_text_to_njd() -> Result<(), Box<dyn Error>> {
+    let jpreprocess = min_dict()?;
+
+    for text in [
+        "日本語文を解析し、音声合成エンジンに渡せる形式に変換します．",
+        "音声ABC合成テスト。未知語、を含む文",
+        "音声合成\nエンジン\tに渡せる形式。\n",
+        "",
+    ] {
+        let njd = jpreprocess.text_to_njd(text)?;
+        let candidates = jpreprocess.text_to_njd_nbest(text, 1)?;
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].1.nodes, njd.nodes, "{}", text);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn ordered_by_cost() -> Result<(), Box<dyn Error>> {
+    let jpreprocess = min_dict()?;
+
+    let text = "日本語文を解析し、音声合成エンジンに渡せる形式に変換します．";
+    let candidates = jpreprocess.text_to_njd_nbest(text, 10)?;
+    // the surfaces are the same, but `、` and `．` may be read as unknown words
+    assert_eq!(candidates.len(), 4);
+    assert!(candidates.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    for i in 1..candidates.len() {
+        assert!(candidates[..i]
+            .iter()
+            .all(|(_, njd)| njd.nodes != candidates[i].1.nodes));
+    }
+
+    let top2 = jpreprocess.text_to_njd_nbest(text, 2)?;
+    assert_eq!(top2.len(), 2);
+    assert!(top2
+        .iter()
+        .zip(&candidates)
+        .all(|(a, b)| a.0 == b.0 && a.1.nodes == b.1.nodes));
+
+    assert!(jpreprocess.text_to_njd_nbest(text, 0)?.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use std::{error::Error, path::PathBuf, str::FromStr};
+
+use jpreprocess::{
+    JPreprocess, JPreprocessConfig, NormalizeStep, ReplaceRules, SystemDictionaryConfig,
+    TextNormalizer, TokenizerOptions,
+};
+
+fn min_dict(normalizer: TextNormalizer) -> Result<JPreprocess, Box<dyn Error>> {
+    Ok(JPreprocess::from_config(JPreprocessConfig {
+        dictionary: SystemDictionaryConfig::File(PathBuf::from("tests/min-dict")),
+        user_dictionary: None,
+        tokenizer: TokenizerOptions {
+            normalizer,
+            ..Default::default()
+        },
+    })?)
+}
+
+fn strings(jpreprocess: &JPreprocess, text: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    Ok(jpreprocess
+        .text_to_njd(text)?
+        .nodes
+        .iter()
+        .map(|node| node.get_string().to_string())
+        .collect())
+}
+
+#[test]
+fn replace_rules() -> Result<(), Box<dyn Error>> {
+    let normalizer = TextNormalizer::naist_jdic().with_step(NormalizeStep::Replace(
+        ReplaceRules::from_str("おんせい\t音声")?,
+    ));
+    let jpreprocess = min_dict(normalizer)?;
+
+    assert_eq!(strings(&jpreprocess, "おんせい合成")?, ["音声", "合成"]);
+
+    Ok(())
+}
+
+#[test]
+fn skip_normalization() -> Result<(), Box<dyn Error>> {
+    let default = min_dict(TextNormalizer::default())?;
+    assert_eq!(strings(&default, "ABC")?.concat(), "ＡＢＣ");
+
+    let skip = min_dict(TextNormalizer::new(vec![]))?;
+    assert_eq!(strings(&skip, "ABC")?.concat(), "ABC");
+
+    Ok(())
+}
@@ -38,6 +38,7 @@ fn test_one(input_text: &'static str) {
     let jpreprocess = JPreprocess::from_config(JPreprocessConfig {
         dictionary: config,
         user_dictionary: None,
+        tokenizer: Default::default(),
     })
     .unwrap();
 
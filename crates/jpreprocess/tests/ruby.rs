@@ -0,0 +1,78 @@
+use std::{error::Error, path::PathBuf};
+
+use jpreprocess::{JPreprocess, JPreprocessConfig, SystemDictionaryConfig};
+use jpreprocess_core::error::JPreprocessErrorKind;
+
+fn min_dict() -> Result<JPreprocess, Box<dyn Error>> {
+    Ok(JPreprocess::from_config(JPreprocessConfig {
+        dictionary: SystemDictionaryConfig::File(PathBuf::from("tests/min-dict")),
+        user_dictionary: None,
+        tokenizer: Default::default(),
+    })?)
+}
+
+#[test]
+fn force_reading() -> Result<(), Box<dyn Error>> {
+    let jpreprocess = min_dict()?;
+
+    let njd = jpreprocess.ruby_text_to_njd("｜音声《おんじょう》合成")?;
+    let plain = jpreprocess.text_to_njd("音声合成")?;
+    assert_eq!(njd.nodes.len(), plain.nodes.len());
+    assert_eq!(njd.nodes[0].get_string(), "音声");
+    assert_eq!(njd.nodes[0].get_pron().to_string(), "オンジョウ");
+    assert_eq!(njd.nodes[0].get_mora_size(), 4);
+    assert_eq!(njd.nodes[0].get_pos(), plain.nodes[0].get_pos());
+    assert_eq!(njd.nodes[1..], plain.nodes[1..]);
+
+    Ok(())
+}
+
+#[test]
+fn join_nodes() -> Result<(), Box<dyn Error>> {
+    let jpreprocess = min_dict()?;
+
+    let mut njd =
+        jpreprocess.ruby_text_to_njd("<ruby>音声合成<rt>オンセイゴーセイ</rt></ruby>エンジン")?;
+    assert_eq!(njd.nodes.len(), 2);
+    assert_eq!(njd.nodes[0].get_string(), "音声合成");
+    assert_eq!(njd.nodes[0].get_mora_size(), 8);
+    assert_eq!(njd.nodes[0].get_acc(), 0);
+    assert_eq!(
+        njd.nodes[1..],
+        jpreprocess.text_to_njd("エンジン")?.nodes[..]
+    );
+
+    njd.preprocess();
+    let pron: String = njd
+        .nodes
+        .iter()
+        .map(|node| node.get_pron().to_string())
+        .collect();
+    assert_eq!(pron, "オンセイゴーセイエンジン");
+
+    Ok(())
+}
+
+#[test]
+fn invalid_reading() -> Result<(), Box<dyn Error>> {
+    let jpreprocess = min_dict()?;
+
+    let err = jpreprocess.ruby_text_to_njd("｜音声《voice》").unwrap_err();
+    assert_eq!(err.kind, JPreprocessErrorKind::PronunciationParseError);
+
+    Ok(())
+}
+
+#[test]
+fn split_word() -> Result<(), Box<dyn Error>> {
+    let jpreprocess = min_dict()?;
+
+    // `音声` crosses the boundary of the annotated part
+    let njd = jpreprocess.ruby_text_to_njd("音｜声合成《せいごうせい》")?;
+    assert_eq!(njd.nodes.len(), 2);
+    assert_eq!(njd.nodes[0], jpreprocess.text_to_njd("音")?.nodes[0]);
+    assert_eq!(njd.nodes[1].get_string(), "声合成");
+    assert_eq!(njd.nodes[1].get_pron().to_string(), "セイゴウセイ");
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use std::{error::Error, path::PathBuf};
+
+use jpreprocess::{JPreprocess, JPreprocessConfig, SystemDictionaryConfig};
+use jpreprocess_core::error::JPreprocessErrorKind;
+
+fn min_dict() -> Result<JPreprocess, Box<dyn Error>> {
+    Ok(JPreprocess::from_config(JPreprocessConfig {
+        dictionary: SystemDictionaryConfig::File(PathBuf::from("tests/min-dict")),
+        user_dictionary: None,
+        tokenizer: Default::default(),
+    })?)
+}
+
+fn strings(njd: &jpreprocess::NJD) -> Vec<&str> {
+    njd.nodes.iter().map(|node| node.get_string()).collect()
+}
+
+#[test]
+fn pauses() -> Result<(), Box<dyn Error>> {
+    let jpreprocess = min_dict()?;
+
+    let njd = jpreprocess.ssml_to_njd(
+        r#"<speak><break/><prosody rate="slow">音声</prosody><break time="300ms"/><sub alias="合成">ごうせい</sub></speak>"#,
+    )?;
+    // no pause at the beginning, and no consecutive pauses
+    assert_eq!(strings(&njd), ["音声", "、", "合成"]);
+    assert!(njd.nodes[1].get_pron().is_touten());
+
+    let njd = jpreprocess.ssml_to_njd(r#"<say-as interpret-as="telephone">03-1234</say-as>"#)?;
+    assert_eq!(strings(&njd), ["０３", "、", "１２３４"]);
+    assert!(njd
+        .nodes
+        .iter()
+        .all(|node| node.get_numerical_reading() == Some(false)));
+
+    Ok(())
+}
+
+#[test]
+fn readings() -> Result<(), Box<dyn Error>> {
+    let jpreprocess = min_dict()?;
+
+    let mut njd = jpreprocess.ssml_to_njd(
+        r#"<phoneme alphabet="x-jpreprocess-kana" ph="オンジョウ">音声</phoneme><say-as interpret-as="characters">合成</say-as>"#,
+    )?;
+    assert_eq!(strings(&njd), ["音声", "合", "成"]);
+    assert_eq!(njd.nodes[0].get_read(), Some("オンジョウ"));
+    njd.preprocess();
+
+    let err = jpreprocess
+        .ssml_to_njd(r#"<phoneme alphabet="x-jpreprocess-kana" ph="abc">音声</phoneme>"#)
+        .unwrap_err();
+    assert_eq!(err.kind, JPreprocessErrorKind::PronunciationParseError);
+
+    Ok(())
+}
@@ -22,6 +22,7 @@ fn system_dictionary() -> Result<(), Box<dyn Error>> {
     let jpreprocess = JPreprocess::from_config(JPreprocessConfig {
         dictionary: config,
         user_dictionary: None,
+        tokenizer: Default::default(),
     })
     .unwrap();
     let njd = jpreprocess.text_to_njd("クーバネティス")?;